@@ -1,11 +1,6 @@
-use std::{
-    io::{self, Stdout, Write, stdout},
-    thread::sleep,
-    time::Duration,
-};
+use std::{io::stdout, time::Duration};
 
-use crossterm::{ExecutableCommand, cursor, terminal};
-use morphing_string::MorphingString;
+use morphing_string::Slideshow;
 
 // A poem by Refaat Alareer. https://ifimustdie.net/
 const LINES: &[&str] = &[
@@ -31,32 +26,12 @@ const LINES: &[&str] = &[
     "",
 ];
 
-const MORPH_STEP_DURATION: Duration = Duration::from_millis(20);
-const LINE_STEP_DURATION: Duration = Duration::from_secs(2);
-
 fn main() {
-    let mut out = stdout();
-    let mut lines = LINES.iter().map(|line| line.to_string()).cycle();
-    let mut line = MorphingString::new("".to_string());
-
-    loop {
-        let next_line = lines.next().expect("non-empty LINES cycled()d endlessly");
-        line.set_target(next_line);
-        clear_and_print(line.value(), &mut out).unwrap();
-
-        while !line.advance().is_complete() {
-            clear_and_print(line.value(), &mut out).unwrap();
-            sleep(MORPH_STEP_DURATION);
-        }
-
-        sleep(LINE_STEP_DURATION);
-    }
-}
-
-fn clear_and_print(line: &String, out: &mut Stdout) -> io::Result<()> {
-    out.execute(cursor::MoveToColumn(0))?;
-    out.execute(terminal::Clear(terminal::ClearType::CurrentLine))?;
-
-    write!(out, "{line}").expect("can write ");
-    out.flush()
+    Slideshow::new(stdout(), LINES.iter().copied())
+        .with_frame_interval(Duration::from_millis(20))
+        // Hold every line for 2 seconds.
+        .with_dwell(Duration::from_secs(2))
+        .with_looping()
+        .run()
+        .unwrap();
 }
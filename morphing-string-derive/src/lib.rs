@@ -0,0 +1,201 @@
+//! `#[derive(Morphing)]` for `morphing-string`. Use it through the `derive` feature of the main
+//! crate rather than depending on this crate directly.
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// Generate a companion `<Name>Morphing` struct holding a `MorphingString` for every `String`
+/// field of a struct with named fields. Other fields are ignored.
+///
+/// The companion struct provides `new(&Name)`, `set_target(&Name)` and `advance_all()`, the latter
+/// advancing every field by one edit and returning the combined `Progress`.
+#[proc_macro_derive(Morphing)]
+pub fn derive_morphing(input: TokenStream) -> TokenStream {
+    match parse_struct(input) {
+        Ok(input) => expand(&input),
+        Err(message) => format!("::core::compile_error!({message:?});")
+            .parse()
+            .expect("compile_error! invocation is valid Rust"),
+    }
+}
+
+struct Struct {
+    visibility: String,
+    name: String,
+    string_fields: Vec<Field>,
+}
+
+struct Field {
+    visibility: String,
+    name: String,
+}
+
+fn parse_struct(input: TokenStream) -> Result<Struct, String> {
+    let mut tokens = input.into_iter().peekable();
+
+    skip_attributes(&mut tokens);
+    let visibility = parse_visibility(&mut tokens);
+
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => {}
+        _ => return Err("#[derive(Morphing)] only supports structs".to_string()),
+    }
+
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => return Err("expected struct name".to_string()),
+    };
+
+    let fields = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group.stream(),
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+            return Err("#[derive(Morphing)] doesn't support generic structs".to_string());
+        }
+        _ => return Err("#[derive(Morphing)] only supports structs with named fields".to_string()),
+    };
+
+    let mut string_fields = Vec::new();
+    for field in split_fields(fields) {
+        let mut tokens = field.into_iter().peekable();
+
+        skip_attributes(&mut tokens);
+        let visibility = parse_visibility(&mut tokens);
+        let name = match tokens.next() {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => return Err("expected field name".to_string()),
+        };
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {}
+            _ => return Err(format!("expected `:` after field `{name}`")),
+        }
+
+        let ty: String = tokens.map(|token| token.to_string()).collect();
+        if is_string_type(&ty) {
+            string_fields.push(Field { visibility, name });
+        }
+    }
+
+    Ok(Struct {
+        visibility,
+        name,
+        string_fields,
+    })
+}
+
+fn skip_attributes(tokens: &mut std::iter::Peekable<impl Iterator<Item = TokenTree>>) {
+    while matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '#') {
+        tokens.next();
+        tokens.next();
+    }
+}
+
+fn parse_visibility(tokens: &mut std::iter::Peekable<impl Iterator<Item = TokenTree>>) -> String {
+    if !matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident.to_string() == "pub") {
+        return String::new();
+    }
+
+    let mut visibility = tokens.next().expect("peeked").to_string();
+    if let Some(TokenTree::Group(group)) = tokens.peek()
+        && group.delimiter() == Delimiter::Parenthesis
+    {
+        visibility.push_str(&tokens.next().expect("peeked").to_string());
+    }
+
+    visibility
+}
+
+/// Split the contents of a struct body on commas that aren't nested inside a field's type.
+fn split_fields(fields: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut split = vec![Vec::new()];
+    let mut angle_depth = 0usize;
+    let mut previous_was_dash = false;
+
+    for token in fields {
+        let mut is_dash = false;
+
+        if let TokenTree::Punct(punct) = &token {
+            match punct.as_char() {
+                '<' => angle_depth += 1,
+                // The `>` of a `->` in a function pointer type doesn't close an angle bracket.
+                '>' if !previous_was_dash => angle_depth = angle_depth.saturating_sub(1),
+                ',' if angle_depth == 0 => {
+                    split.push(Vec::new());
+                    continue;
+                }
+                '-' => is_dash = true,
+                _ => {}
+            }
+        }
+
+        previous_was_dash = is_dash;
+        split.last_mut().expect("never empty").push(token);
+    }
+
+    split.retain(|field| !field.is_empty());
+    split
+}
+
+fn is_string_type(ty: &str) -> bool {
+    matches!(
+        ty,
+        "String" | "std::string::String" | "::std::string::String" | "alloc::string::String"
+    )
+}
+
+fn expand(input: &Struct) -> TokenStream {
+    let Struct {
+        visibility,
+        name,
+        string_fields,
+    } = input;
+
+    let mut fields = String::new();
+    let mut new = String::new();
+    let mut set_target = String::new();
+    let mut advance_all = String::new();
+
+    for Field { visibility, name } in string_fields {
+        fields.push_str(&format!(
+            "{visibility} {name}: ::morphing_string::MorphingString,"
+        ));
+        new.push_str(&format!(
+            "{name}: ::morphing_string::MorphingString::new(\
+                ::core::clone::Clone::clone(&value.{name})),"
+        ));
+        set_target.push_str(&format!(
+            "self.{name}.set_target(::core::clone::Clone::clone(&target.{name}));"
+        ));
+        advance_all.push_str(&format!(
+            "let field_progress = self.{name}.advance();\
+            progress.total_edits += field_progress.total_edits;\
+            progress.remaining_edits += field_progress.remaining_edits;"
+        ));
+    }
+
+    format!(
+        "{visibility} struct {name}Morphing {{ {fields} }}
+
+        impl {name}Morphing {{
+            #[allow(unused_variables)]
+            pub fn new(value: &{name}) -> Self {{
+                Self {{ {new} }}
+            }}
+
+            #[allow(unused_variables)]
+            pub fn set_target(&mut self, target: &{name}) {{
+                {set_target}
+            }}
+
+            pub fn advance_all(&mut self) -> ::morphing_string::Progress {{
+                #[allow(unused_mut)]
+                let mut progress = ::morphing_string::Progress {{
+                    total_edits: 0,
+                    remaining_edits: 0,
+                }};
+                {advance_all}
+                progress
+            }}
+        }}"
+    )
+    .parse()
+    .expect("generated code is valid Rust")
+}
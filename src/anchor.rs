@@ -0,0 +1,89 @@
+/// Which part of the string a morph keeps stable when it has the choice, i.e. when several
+/// scripts with the lowest cost exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Anchor {
+    /// Align the strings from the start, e.g. for left-aligned labels, keeping their head fixed
+    /// while the tail morphs.
+    Left,
+    /// Align the strings from the end, e.g. for counters, right-aligned numbers and sizes with
+    /// units, keeping their tail fixed while the head morphs.
+    #[default]
+    Right,
+    /// Align the middles of the strings, e.g. for centered titles, morphing both ends.
+    Center,
+}
+
+impl Anchor {
+    /// The diagonal of the edit distance matrix, i.e. the difference between the indexes of paired
+    /// up chars of the target and of the start, that keeps the anchored part aligned.
+    pub(crate) fn diagonal(self, start_len: usize, target_len: usize) -> isize {
+        let len_delta = target_len as isize - start_len as isize;
+        match self {
+            Anchor::Left => 0,
+            Anchor::Right => len_delta,
+            Anchor::Center => len_delta / 2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+    use crate::{
+        edit::Edit,
+        levenshtein::{Costs, compute_weighted_edit_sequence},
+        tie_break::TieBreak,
+    };
+
+    fn frames(start: &str, target: &str, anchor: Anchor) -> Vec<String> {
+        let edits: VecDeque<Edit> = compute_weighted_edit_sequence(
+            start,
+            target,
+            &Costs::default(),
+            anchor,
+            TieBreak::default(),
+        );
+        let mut frames = vec![start.to_string()];
+        for edit in &edits {
+            frames.push(edit.apply(frames.last().expect("non-empty")));
+        }
+        frames
+    }
+
+    #[test]
+    fn anchored_part_stays_aligned() {
+        // The units digit morphs into the units digit.
+        assert_eq!(
+            frames("9 MB", "10 MB", Anchor::Right),
+            vec!["9 MB", "19 MB", "10 MB"]
+        );
+        assert_eq!(
+            frames("9 MB", "10 MB", Anchor::Left),
+            vec!["9 MB", "1 MB", "10 MB"]
+        );
+        assert_eq!(
+            frames("ab", "abab", Anchor::Left),
+            vec!["ab", "aba", "abab"]
+        );
+        assert_eq!(
+            frames("abc", "xxabcxx", Anchor::Center),
+            vec!["abc", "xabc", "xxabc", "xxabcx", "xxabcxx"]
+        );
+    }
+
+    #[test]
+    fn every_anchor_reaches_the_target() {
+        for (start, target) in [
+            ("kitten", "sitting"),
+            ("", "abc"),
+            ("abc", ""),
+            ("ab cd", "dc"),
+        ] {
+            for anchor in [Anchor::Left, Anchor::Right, Anchor::Center] {
+                assert_eq!(frames(start, target, anchor).last().unwrap(), target);
+            }
+        }
+    }
+}
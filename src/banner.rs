@@ -0,0 +1,83 @@
+use figlet_rs::FIGfont;
+
+use crate::{CellChange, MorphingCanvas, Progress, Sweep};
+
+/// Short text rendered in big figlet letters and morphed at the level of the art, for dramatic
+/// banner transitions on splash screens.
+pub struct MorphingBanner {
+    canvas: MorphingCanvas,
+    font: FIGfont,
+}
+
+impl MorphingBanner {
+    /// A banner showing `text` in the standard figlet font.
+    pub fn new(text: &str) -> Self {
+        let font = FIGfont::standard().expect("the standard font is bundled");
+        Self {
+            canvas: MorphingCanvas::new(&render(&font, text)),
+            font,
+        }
+    }
+
+    /// Render subsequent targets in `font` instead, e.g. one loaded with
+    /// [`FIGfont::from_content`].
+    pub fn with_font(mut self, font: FIGfont) -> Self {
+        self.font = font;
+        self
+    }
+
+    pub fn with_sweep(mut self, sweep: Sweep) -> Self {
+        self.canvas = self.canvas.with_sweep(sweep);
+        self
+    }
+
+    pub fn set_target(&mut self, text: &str) {
+        self.canvas.set_target(&render(&self.font, text));
+    }
+
+    pub fn advance(&mut self) -> Progress {
+        self.canvas.advance()
+    }
+
+    pub fn progress(&self) -> Progress {
+        self.canvas.progress()
+    }
+
+    /// See [`MorphingCanvas::last_changes`].
+    pub fn last_changes(&self) -> &[CellChange] {
+        self.canvas.last_changes()
+    }
+
+    /// The current, possibly intermediate, rows of the art.
+    pub fn rows(&self) -> impl Iterator<Item = String> {
+        self.canvas.rows()
+    }
+}
+
+/// `text` as figlet art, or nothing if the font can't render it.
+fn render(font: &FIGfont, text: &str) -> String {
+    font.convert(text)
+        .map(|figure| figure.to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn banner_morphs_into_the_art_of_the_target() {
+        let mut banner = MorphingBanner::new("hi");
+        banner.set_target("ok");
+        while !banner.advance().is_complete() {}
+
+        let font = FIGfont::standard().unwrap();
+        let art = render(&font, "ok");
+        let rows: Vec<String> = banner
+            .rows()
+            .map(|row| row.trim_end().to_string())
+            .collect();
+        let expected: Vec<&str> = art.lines().map(str::trim_end).collect();
+        assert_eq!(rows, expected);
+    }
+}
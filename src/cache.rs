@@ -0,0 +1,119 @@
+use std::collections::VecDeque;
+
+use crate::edit::Edit;
+
+/// A least-recently-used cache of edit sequences keyed by `(start, target)` pairs.
+///
+/// Caches are expected to be small (a handful of states toggled back and forth) so entries are
+/// kept in a plain list ordered from least to most recently used.
+#[derive(Debug, Clone)]
+pub(crate) struct EditCache {
+    capacity: usize,
+    entries: VecDeque<Entry>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    start: String,
+    target: String,
+    edits: VecDeque<Edit>,
+}
+
+impl EditCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Return the edit sequence turning `start` into `target`, computing and remembering it if it
+    /// isn't cached yet with `compute`, which is expected to plan the same way for every call.
+    pub fn get_or_compute(
+        &mut self,
+        start: &str,
+        target: &str,
+        compute: impl FnOnce(&str, &str) -> VecDeque<Edit>,
+    ) -> VecDeque<Edit> {
+        let position = self
+            .entries
+            .iter()
+            .position(|entry| entry.start == start && entry.target == target);
+
+        if let Some(position) = position {
+            // Move the hit to the most recently used end.
+            let entry = self
+                .entries
+                .remove(position)
+                .expect("position is in bounds");
+            let edits = entry.edits.clone();
+            self.entries.push_back(entry);
+            return edits;
+        }
+
+        let edits = compute(start, target);
+
+        if self.capacity > 0 {
+            if self.entries.len() == self.capacity {
+                self.entries.pop_front();
+            }
+            self.entries.push_back(Entry {
+                start: start.to_string(),
+                target: target.to_string(),
+                edits: edits.clone(),
+            });
+        }
+
+        edits
+    }
+
+    #[cfg(test)]
+    fn contains(&self, start: &str, target: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.start == start && entry.target == target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levenshtein::compute_edit_sequence;
+
+    #[test]
+    fn cached_edits_match_computed() {
+        let mut cache = EditCache::new(2);
+
+        let first = cache.get_or_compute("kitten", "mittens", compute_edit_sequence);
+        let second = cache.get_or_compute("kitten", "mittens", compute_edit_sequence);
+
+        assert_eq!(first, compute_edit_sequence("kitten", "mittens"));
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn least_recently_used_is_evicted() {
+        let mut cache = EditCache::new(2);
+
+        cache.get_or_compute("a", "b", compute_edit_sequence);
+        cache.get_or_compute("b", "c", compute_edit_sequence);
+        // Touch the first entry so the second becomes the least recently used one.
+        cache.get_or_compute("a", "b", compute_edit_sequence);
+        cache.get_or_compute("c", "d", compute_edit_sequence);
+
+        assert!(cache.contains("a", "b"));
+        assert!(!cache.contains("b", "c"));
+        assert!(cache.contains("c", "d"));
+    }
+
+    #[test]
+    fn zero_capacity_stores_nothing() {
+        let mut cache = EditCache::new(0);
+
+        assert_eq!(
+            cache.get_or_compute("a", "b", compute_edit_sequence).len(),
+            1
+        );
+        assert!(!cache.contains("a", "b"));
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::{HashMap, VecDeque};
+
+use unicode_normalization::{UnicodeNormalization, char::canonical_combining_class};
+
+use crate::{edit::Edit, lines::private_use};
+
+/// Plan edits from `start` to `target` with `plan`, comparing clusters of a base char and the
+/// combining marks following it by canonical equivalence, e.g. so composed and decomposed `é`
+/// count as equal. Kept clusters encoded differently than in the target are re-encoded by the
+/// last edits, so the result still reproduces the target exactly.
+pub(crate) fn plan(
+    start: &str,
+    target: &str,
+    plan: impl FnOnce(&str, &str) -> VecDeque<Edit>,
+) -> VecDeque<Edit> {
+    let start_clusters = clusters(start);
+    let target_clusters = clusters(target);
+
+    // Stand every canonically distinct cluster in for a private use char, and plan with those.
+    let mut ids: HashMap<String, char> = HashMap::new();
+    // The encoding of every distinct cluster inserted or substituted in.
+    let mut encodings: HashMap<char, &str> = HashMap::new();
+    let mut stand_in = |cluster: &str| {
+        let next = private_use(ids.len())?;
+        Some(*ids.entry(cluster.nfc().collect()).or_insert(next))
+    };
+    let Some(start_stand_ins) = start_clusters
+        .iter()
+        .map(|cluster| stand_in(cluster))
+        .collect::<Option<String>>()
+    else {
+        return plan(start, target);
+    };
+    let mut target_stand_ins = String::new();
+    for cluster in &target_clusters {
+        let Some(id) = stand_in(cluster) else {
+            return plan(start, target);
+        };
+        encodings.entry(id).or_insert(cluster);
+        target_stand_ins.push(id);
+    }
+
+    let mut clusters: Vec<&str> = start_clusters;
+    let mut edits = VecDeque::new();
+    let offset = |clusters: &[&str], index: usize| -> usize {
+        clusters[..index]
+            .iter()
+            .map(|cluster| cluster.chars().count())
+            .sum()
+    };
+    for edit in plan(&start_stand_ins, &target_stand_ins) {
+        match edit {
+            Edit::Insert { c, index } => {
+                let offset = offset(&clusters, index);
+                let encoding = encodings[&c];
+                edits.extend(
+                    (offset..)
+                        .zip(encoding.chars())
+                        .map(|(index, c)| Edit::Insert { c, index }),
+                );
+                clusters.insert(index, encoding);
+            }
+            Edit::Delete { index } => {
+                let offset = offset(&clusters, index);
+                let deleted = clusters.remove(index);
+                edits.extend(deleted.chars().map(|_| Edit::Delete { index: offset }));
+            }
+            Edit::Substitute { c, index } => {
+                let encoding = encodings[&c];
+                reencode(
+                    &mut edits,
+                    offset(&clusters, index),
+                    clusters[index],
+                    encoding,
+                );
+                clusters[index] = encoding;
+            }
+        }
+    }
+    for (index, target_cluster) in target_clusters.iter().enumerate() {
+        if clusters[index] != *target_cluster {
+            reencode(
+                &mut edits,
+                offset(&clusters, index),
+                clusters[index],
+                target_cluster,
+            );
+            clusters[index] = target_cluster;
+        }
+    }
+
+    edits
+}
+
+/// The number of leading `edits` after which `start` is canonically equivalent to the value the
+/// edits end with, i.e. the ones that change it visibly when the rest only re-encode it.
+pub(crate) fn visible_edits(start: &str, edits: impl Iterator<Item = Edit> + Clone) -> usize {
+    let end = edits
+        .clone()
+        .fold(start.to_string(), |value, edit| edit.apply(&value));
+    let end: String = end.nfc().collect();
+
+    let mut value = start.to_string();
+    let mut applied = 0;
+    for edit in edits {
+        if value.nfc().eq(end.chars()) {
+            return applied;
+        }
+        value = edit.apply(&value);
+        applied += 1;
+    }
+    applied
+}
+
+/// Split `string` into base chars, each followed by its combining marks.
+fn clusters(string: &str) -> Vec<&str> {
+    let mut clusters = Vec::new();
+    let mut cluster_start = 0;
+    for (offset, c) in string.char_indices() {
+        if offset > 0 && canonical_combining_class(c) == 0 {
+            clusters.push(&string[cluster_start..offset]);
+            cluster_start = offset;
+        }
+    }
+    if !string.is_empty() {
+        clusters.push(&string[cluster_start..]);
+    }
+    clusters
+}
+
+/// Push the edits turning `old`, found at char `offset`, into `new`.
+fn reencode(edits: &mut VecDeque<Edit>, offset: usize, old: &str, new: &str) {
+    let old: Vec<char> = old.chars().collect();
+    let new: Vec<char> = new.chars().collect();
+    let common = old.len().min(new.len());
+
+    for (index, (&old, &new)) in (offset..).zip(old.iter().zip(&new)) {
+        if old != new {
+            edits.push_back(Edit::Substitute { c: new, index });
+        }
+    }
+    let end = offset + common;
+    edits.extend(
+        (end..)
+            .zip(&new[common..])
+            .map(|(index, &c)| Edit::Insert { c, index }),
+    );
+    edits.extend(old[common..].iter().map(|_| Edit::Delete { index: end }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MorphingString, levenshtein::compute_edit_sequence};
+
+    #[test]
+    fn equivalent_clusters_are_kept() {
+        let start = "caf\u{e9}";
+        let target = "cafe\u{301}s";
+        let edits = plan(start, target, compute_edit_sequence);
+
+        let mut value = start.to_string();
+        for edit in &edits {
+            value = edit.apply(&value);
+        }
+        assert_eq!(value, target);
+        // Inserting the `s` is the only visible change.
+        assert_eq!(visible_edits(start, edits.iter().copied()), 1);
+    }
+
+    #[test]
+    fn reencoding_is_a_single_step() {
+        let mut morph = MorphingString::new("caf\u{e9}".to_string()).with_canonical_equivalence();
+        morph.set_target("cafe\u{301}s".to_string());
+
+        assert!(morph.advance().is_complete());
+        assert_eq!(morph.value(), "cafe\u{301}s");
+    }
+}
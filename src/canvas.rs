@@ -0,0 +1,205 @@
+use std::collections::VecDeque;
+
+use crate::Progress;
+
+/// The order in which the cells of a [`MorphingCanvas`] change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sweep {
+    /// A diagonal wavefront moving from the top left corner to the bottom right one.
+    #[default]
+    Diagonal,
+    /// Row by row from the top.
+    Rows,
+    /// Outwards from the center.
+    Radial,
+}
+
+/// A cell of a [`MorphingCanvas`] changed by an advance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellChange {
+    pub row: usize,
+    pub column: usize,
+    pub c: char,
+}
+
+/// A rectangular grid of chars, e.g. ASCII art or a banner, morphed cell by cell as a whole
+/// rather than row by row, so the transition sweeps over it coherently.
+///
+/// Grids of different sizes are padded with spaces to cover both, and the canvas takes the size
+/// of the target once the morph completes.
+pub struct MorphingCanvas {
+    cells: Vec<Vec<char>>,
+    sweep: Sweep,
+    // Changes yet to apply, grouped into the steps applying them.
+    pending: VecDeque<Vec<CellChange>>,
+    total_changes: usize,
+    remaining_changes: usize,
+    last_changes: Vec<CellChange>,
+    // The size of the target, which the canvas is cropped to once complete.
+    target_size: (usize, usize),
+}
+
+impl MorphingCanvas {
+    pub fn new(art: &str) -> Self {
+        let cells = grid(art);
+        Self {
+            target_size: size(&cells),
+            cells,
+            sweep: Sweep::default(),
+            pending: VecDeque::new(),
+            total_changes: 0,
+            remaining_changes: 0,
+            last_changes: Vec::new(),
+        }
+    }
+
+    pub fn with_sweep(mut self, sweep: Sweep) -> Self {
+        self.sweep = sweep;
+        self
+    }
+
+    /// Morph towards `art`, starting from the current, possibly intermediate, grid.
+    pub fn set_target(&mut self, art: &str) {
+        let target = grid(art);
+        self.target_size = size(&target);
+        let (height, width) = size(&self.cells);
+        let height = height.max(self.target_size.0);
+        let width = width.max(self.target_size.1);
+        pad(&mut self.cells, height, width);
+
+        let mut changes: Vec<CellChange> = Vec::new();
+        for (row, cells) in self.cells.iter().enumerate() {
+            for (column, &current) in cells.iter().enumerate() {
+                let c = target
+                    .get(row)
+                    .and_then(|target| target.get(column))
+                    .copied()
+                    .unwrap_or(' ');
+                if c != current {
+                    changes.push(CellChange { row, column, c });
+                }
+            }
+        }
+
+        let key = |change: &CellChange| -> usize {
+            match self.sweep {
+                Sweep::Diagonal => change.row + change.column,
+                Sweep::Rows => change.row,
+                Sweep::Radial => {
+                    // Twice the distance from the center, in steps of a cell.
+                    let row = (2 * change.row + 1).abs_diff(height);
+                    let column = (2 * change.column + 1).abs_diff(width);
+                    (row * row + column * column).isqrt()
+                }
+            }
+        };
+        changes.sort_by_key(key);
+
+        self.pending.clear();
+        for change in &changes {
+            match self.pending.back_mut() {
+                Some(step) if key(&step[0]) == key(change) => step.push(*change),
+                _ => self.pending.push_back(vec![*change]),
+            }
+        }
+        self.total_changes = changes.len();
+        self.remaining_changes = changes.len();
+        self.crop_if_complete();
+    }
+
+    /// Change the next group of cells the sweep reaches.
+    pub fn advance(&mut self) -> Progress {
+        self.last_changes = self.pending.pop_front().unwrap_or_default();
+        for change in &self.last_changes {
+            self.cells[change.row][change.column] = change.c;
+        }
+        self.remaining_changes -= self.last_changes.len();
+        self.crop_if_complete();
+        self.progress()
+    }
+
+    pub fn progress(&self) -> Progress {
+        Progress {
+            total_edits: self.total_changes,
+            remaining_edits: self.remaining_changes,
+        }
+    }
+
+    /// The cells the last [`MorphingCanvas::advance`] changed, e.g. to redraw only those.
+    pub fn last_changes(&self) -> &[CellChange] {
+        &self.last_changes
+    }
+
+    /// The current, possibly intermediate, rows of the grid.
+    pub fn rows(&self) -> impl Iterator<Item = String> {
+        self.cells.iter().map(|row| row.iter().collect())
+    }
+
+    fn crop_if_complete(&mut self) {
+        if self.pending.is_empty() {
+            let (height, width) = self.target_size;
+            self.cells.truncate(height);
+            for row in &mut self.cells {
+                row.truncate(width);
+            }
+        }
+    }
+}
+
+/// The lines of `art` padded with spaces to the longest one.
+fn grid(art: &str) -> Vec<Vec<char>> {
+    let mut cells: Vec<Vec<char>> = art.lines().map(|line| line.chars().collect()).collect();
+    let (height, width) = size(&cells);
+    pad(&mut cells, height, width);
+    cells
+}
+
+fn size(cells: &[Vec<char>]) -> (usize, usize) {
+    (cells.len(), cells.iter().map(Vec::len).max().unwrap_or(0))
+}
+
+fn pad(cells: &mut Vec<Vec<char>>, height: usize, width: usize) {
+    cells.resize_with(height, Vec::new);
+    for row in cells {
+        row.resize(width, ' ');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagonal_sweep_changes_a_wavefront_per_step() {
+        let mut canvas = MorphingCanvas::new("..\n..");
+        canvas.set_target("##\n##");
+
+        let mut frames = Vec::new();
+        while !canvas.advance().is_complete() {
+            frames.push(canvas.rows().collect::<Vec<_>>().join("\n"));
+        }
+        frames.push(canvas.rows().collect::<Vec<_>>().join("\n"));
+
+        assert_eq!(frames, ["#.\n..", "##\n#.", "##\n##"]);
+        assert_eq!(
+            canvas.last_changes(),
+            [CellChange {
+                row: 1,
+                column: 1,
+                c: '#'
+            }]
+        );
+    }
+
+    #[test]
+    fn canvas_takes_the_size_of_the_target() {
+        let mut canvas = MorphingCanvas::new("abc\nd").with_sweep(Sweep::Radial);
+        canvas.set_target("x\ny\nz");
+        while !canvas.advance().is_complete() {
+            assert_eq!(canvas.rows().count(), 3);
+            assert!(canvas.rows().all(|row| row.chars().count() == 3));
+        }
+
+        assert_eq!(canvas.rows().collect::<Vec<_>>(), ["x", "y", "z"]);
+    }
+}
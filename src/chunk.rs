@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+
+use crate::edit::Edit;
+
+/// A run of chars a script keeps, deletes or inserts, the representation of diffs elsewhere,
+/// e.g. in diff-match-patch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Chunk {
+    Equal(String),
+    Delete(String),
+    Insert(String),
+}
+
+/// The chunks `edits` keep, delete and insert when applied to `start`. Substituted chars count as
+/// deleted and inserted. Every deletion comes before the insertion next to it.
+pub(crate) fn chunks(start: &str, edits: impl IntoIterator<Item = Edit>) -> Vec<Chunk> {
+    let start_chars: Vec<char> = start.chars().collect();
+    // The index in the start of every char of the evolving string, or None for touched ones.
+    let mut origins: Vec<Option<usize>> = (0..start_chars.len()).map(Some).collect();
+    let mut target_chars: Vec<char> = start_chars.clone();
+
+    for edit in edits {
+        match edit {
+            Edit::Insert { c, index } => {
+                origins.insert(index, None);
+                target_chars.insert(index, c);
+            }
+            Edit::Delete { index } => {
+                origins.remove(index);
+                target_chars.remove(index);
+            }
+            Edit::Substitute { c, index } => {
+                origins[index] = None;
+                target_chars[index] = c;
+            }
+        }
+    }
+
+    let mut chunks = Vec::new();
+    let mut i = 0;
+    let mut j = 0;
+    let kept = origins
+        .iter()
+        .enumerate()
+        .filter_map(|(j, origin)| origin.map(|i| (i, j)));
+    // Kept chars keep their order, so everything between two of them is deleted or inserted.
+    for (next_i, next_j) in kept.chain([(start_chars.len(), target_chars.len())]) {
+        push(
+            &mut chunks,
+            Chunk::Delete(start_chars[i..next_i].iter().collect()),
+        );
+        push(
+            &mut chunks,
+            Chunk::Insert(target_chars[j..next_j].iter().collect()),
+        );
+        if next_i < start_chars.len() {
+            push(&mut chunks, Chunk::Equal(start_chars[next_i].to_string()));
+        }
+        i = next_i + 1;
+        j = next_j + 1;
+    }
+
+    chunks
+}
+
+/// Append `chunk` to `chunks`, merging it into the last one of the same kind and skipping it if
+/// it's empty.
+pub(crate) fn push(chunks: &mut Vec<Chunk>, chunk: Chunk) {
+    if chunk.text().is_empty() {
+        return;
+    }
+    match (chunks.last_mut(), chunk) {
+        (Some(Chunk::Equal(last)), Chunk::Equal(text))
+        | (Some(Chunk::Delete(last)), Chunk::Delete(text))
+        | (Some(Chunk::Insert(last)), Chunk::Insert(text)) => last.push_str(&text),
+        (_, chunk) => chunks.push(chunk),
+    }
+}
+
+/// The edits turning the deleted and kept chunks into the inserted and kept ones. Deletions
+/// followed by insertions become substitutions as far as possible.
+pub(crate) fn edits(chunks: &[Chunk]) -> VecDeque<Edit> {
+    let mut edits = VecDeque::new();
+    let mut index = 0;
+    let mut deleted = 0;
+
+    for chunk in chunks {
+        match chunk {
+            Chunk::Equal(text) => {
+                delete(&mut edits, index, deleted);
+                deleted = 0;
+                index += text.chars().count();
+            }
+            Chunk::Delete(text) => {
+                delete(&mut edits, index, deleted);
+                deleted = text.chars().count();
+            }
+            Chunk::Insert(text) => {
+                for c in text.chars() {
+                    if deleted > 0 {
+                        edits.push_back(Edit::Substitute { c, index });
+                        deleted -= 1;
+                    } else {
+                        edits.push_back(Edit::Insert { c, index });
+                    }
+                    index += 1;
+                }
+            }
+        }
+    }
+    delete(&mut edits, index, deleted);
+
+    edits
+}
+
+fn delete(edits: &mut VecDeque<Edit>, index: usize, count: usize) {
+    edits.extend((0..count).map(|_| Edit::Delete { index }));
+}
+
+impl Chunk {
+    pub fn text(&self) -> &str {
+        match self {
+            Chunk::Equal(text) | Chunk::Delete(text) | Chunk::Insert(text) => text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levenshtein::compute_edit_sequence;
+
+    #[test]
+    fn chunks_round_trip() {
+        for (start, target) in [
+            ("kitten", "sitting"),
+            ("", "abc"),
+            ("abc", ""),
+            ("ab cd", "dc"),
+        ] {
+            let chunks = chunks(start, compute_edit_sequence(start, target));
+
+            let mut value = start.to_string();
+            for edit in edits(&chunks) {
+                value = edit.apply(&value);
+            }
+            assert_eq!(value, target);
+        }
+    }
+
+    #[test]
+    fn substitutions_are_deleted_and_inserted() {
+        assert_eq!(
+            chunks("kitten", compute_edit_sequence("kitten", "sitting")),
+            vec![
+                Chunk::Delete("k".to_string()),
+                Chunk::Insert("s".to_string()),
+                Chunk::Equal("itt".to_string()),
+                Chunk::Delete("e".to_string()),
+                Chunk::Insert("i".to_string()),
+                Chunk::Equal("n".to_string()),
+                Chunk::Insert("g".to_string()),
+            ]
+        );
+    }
+}
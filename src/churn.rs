@@ -0,0 +1,92 @@
+use crate::{MorphingString, edit::Edit, script::EditScript};
+
+/// An estimate of how visually disruptive a morph is. See [`EditScript::churn`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Churn {
+    /// The fraction of chars, of the start and inserted ones, that get inserted, deleted or
+    /// substituted. Between 0 and 1.
+    pub touched: f64,
+    /// How far the chars that are left alone move in total, in chars.
+    pub index_shift: usize,
+    /// The length of the target minus the length of the start, in chars.
+    pub length_delta: isize,
+}
+
+impl EditScript {
+    /// Estimate how disruptive morphing `start` with this script is, e.g. to decide between
+    /// morphing and swapping the value instantly.
+    pub fn churn(&self, start: &str) -> Churn {
+        churn(start, self.iter())
+    }
+}
+
+impl MorphingString {
+    /// The [`Churn`] of the remaining edits of the current morph.
+    pub fn churn(&self) -> Churn {
+        churn(
+            &self.current_value,
+            self.remaining_edits.iter().map(|edit| edit.unpack()),
+        )
+    }
+}
+
+fn churn(start: &str, edits: impl IntoIterator<Item = Edit>) -> Churn {
+    let start_len = start.chars().count();
+    // The index in the start of every char, or None for touched ones.
+    let mut origins: Vec<Option<usize>> = (0..start_len).map(Some).collect();
+    let mut inserted = 0;
+
+    for edit in edits {
+        match edit {
+            Edit::Insert { index, .. } => {
+                origins.insert(index, None);
+                inserted += 1;
+            }
+            Edit::Delete { index } => {
+                origins.remove(index);
+            }
+            Edit::Substitute { index, .. } => origins[index] = None,
+        }
+    }
+
+    let untouched = origins.iter().flatten().count();
+    let index_shift = origins
+        .iter()
+        .enumerate()
+        .filter_map(|(index, origin)| origin.map(|origin| index.abs_diff(origin)))
+        .sum();
+    let all = start_len + inserted;
+
+    Churn {
+        touched: if all == 0 {
+            0.0
+        } else {
+            (all - untouched) as f64 / all as f64
+        },
+        index_shift,
+        length_delta: origins.len() as isize - start_len as isize,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff;
+
+    #[test]
+    fn prepending_shifts_everything() {
+        let churn = diff("bcd", "abcd").churn("bcd");
+
+        assert_eq!(churn.touched, 0.25);
+        assert_eq!(churn.index_shift, 3);
+        assert_eq!(churn.length_delta, 1);
+    }
+
+    #[test]
+    fn unrelated_strings_are_fully_touched() {
+        let churn = diff("abc", "xyz").churn("abc");
+
+        assert_eq!(churn.touched, 1.0);
+        assert_eq!(churn.index_shift, 0);
+        assert_eq!(diff("", "").churn("").touched, 0.0);
+    }
+}
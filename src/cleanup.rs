@@ -0,0 +1,225 @@
+use std::collections::VecDeque;
+
+use crate::{
+    chunk::{self, Chunk},
+    edit::Edit,
+    script::EditScript,
+};
+
+impl EditScript {
+    /// An equivalent script for the same `start` that reads like an intentional edit rather than
+    /// scattered char noise, in the spirit of diff-match-patch's semantic cleanup: unchanged runs
+    /// no longer than the changes around them are replaced too, and insertions or deletions that
+    /// can slide are moved to word edges. The result usually has more edits.
+    pub fn cleanup_semantic(&self, start: &str) -> EditScript {
+        cleanup_edits(start, self.iter()).into()
+    }
+}
+
+/// See [`EditScript::cleanup_semantic`].
+pub(crate) fn cleanup_edits(start: &str, edits: impl IntoIterator<Item = Edit>) -> VecDeque<Edit> {
+    chunk::edits(&cleanup(chunk::chunks(start, edits)))
+}
+
+pub(crate) fn cleanup(chunks: Vec<Chunk>) -> Vec<Chunk> {
+    let mut chunks = normalize(chunks);
+    while let Some(eliminated) = eliminate_small_equality(&chunks) {
+        chunks = normalize(eliminated);
+    }
+    normalize(align_to_words(chunks))
+}
+
+/// Merge the deletions and insertions between every two equalities into a single deletion
+/// followed by a single insertion.
+fn normalize(chunks: Vec<Chunk>) -> Vec<Chunk> {
+    let mut normalized = Vec::with_capacity(chunks.len());
+    let mut deleted = String::new();
+    let mut inserted = String::new();
+
+    for chunk in chunks {
+        match chunk {
+            Chunk::Equal(text) => {
+                chunk::push(&mut normalized, Chunk::Delete(std::mem::take(&mut deleted)));
+                chunk::push(
+                    &mut normalized,
+                    Chunk::Insert(std::mem::take(&mut inserted)),
+                );
+                chunk::push(&mut normalized, Chunk::Equal(text));
+            }
+            Chunk::Delete(text) => deleted.push_str(&text),
+            Chunk::Insert(text) => inserted.push_str(&text),
+        }
+    }
+    chunk::push(&mut normalized, Chunk::Delete(deleted));
+    chunk::push(&mut normalized, Chunk::Insert(inserted));
+
+    normalized
+}
+
+/// Replace the first equality surrounded by changes at least as long as itself on both sides with
+/// a deletion and an insertion.
+fn eliminate_small_equality(chunks: &[Chunk]) -> Option<Vec<Chunk>> {
+    // The longer of the deleted and inserted text around an equality.
+    let changed = |chunks: &[Chunk]| {
+        let (mut deleted, mut inserted) = (0, 0);
+        for chunk in chunks
+            .iter()
+            .take_while(|chunk| !matches!(chunk, Chunk::Equal(_)))
+        {
+            match chunk {
+                Chunk::Delete(text) => deleted += text.chars().count(),
+                Chunk::Insert(text) => inserted += text.chars().count(),
+                Chunk::Equal(_) => {}
+            }
+        }
+        deleted.max(inserted)
+    };
+
+    let position = (0..chunks.len()).find(|&position| {
+        let Chunk::Equal(text) = &chunks[position] else {
+            return false;
+        };
+        let len = text.chars().count();
+        let before: Vec<Chunk> = chunks[..position].iter().rev().cloned().collect();
+        let after = &chunks[position + 1..];
+
+        len <= changed(&before) && len <= changed(after)
+    })?;
+
+    let mut eliminated = chunks.to_vec();
+    let text = eliminated[position].text().to_string();
+    eliminated.splice(
+        position..=position,
+        [Chunk::Delete(text.clone()), Chunk::Insert(text)],
+    );
+    Some(eliminated)
+}
+
+/// Slide every lone insertion or deletion between two equalities to the position where it best
+/// lines up with word edges.
+fn align_to_words(mut chunks: Vec<Chunk>) -> Vec<Chunk> {
+    for position in 1..chunks.len().saturating_sub(1) {
+        let (Chunk::Equal(before), Chunk::Equal(after)) =
+            (&chunks[position - 1], &chunks[position + 1])
+        else {
+            continue;
+        };
+        if matches!(chunks[position], Chunk::Equal(_)) {
+            continue;
+        }
+
+        let mut before: Vec<char> = before.chars().collect();
+        let mut edit: Vec<char> = chunks[position].text().chars().collect();
+        let mut after: Vec<char> = after.chars().collect();
+
+        // Slide all the way to the left, then try every position to the right. Ties go to the
+        // rightmost position, like in diff-match-patch.
+        while before.last().is_some_and(|&c| edit.last() == Some(&c)) {
+            let c = before.pop().expect("non-empty");
+            edit.insert(0, c);
+            after.insert(0, edit.pop().expect("non-empty"));
+        }
+        let mut best = (
+            score(&before, &edit, &after),
+            before.clone(),
+            edit.clone(),
+            after.clone(),
+        );
+        while !after.is_empty() && after[0] == edit[0] {
+            before.push(edit.remove(0));
+            edit.push(after.remove(0));
+            let score = score(&before, &edit, &after);
+            if score >= best.0 {
+                best = (score, before.clone(), edit.clone(), after.clone());
+            }
+        }
+
+        let (_, before, edit, after) = best;
+        chunks[position - 1] = Chunk::Equal(before.into_iter().collect());
+        chunks[position] = match chunks[position] {
+            Chunk::Delete(_) => Chunk::Delete(edit.into_iter().collect()),
+            _ => Chunk::Insert(edit.into_iter().collect()),
+        };
+        chunks[position + 1] = Chunk::Equal(after.into_iter().collect());
+    }
+
+    chunks.retain(|chunk| !chunk.text().is_empty());
+    chunks
+}
+
+/// How well the boundaries around `edit` line up with word edges.
+fn score(before: &[char], edit: &[char], after: &[char]) -> usize {
+    boundary(before.last(), edit.first()) + boundary(edit.last(), after.first())
+}
+
+fn boundary(left: Option<&char>, right: Option<&char>) -> usize {
+    let (Some(&left), Some(&right)) = (left, right) else {
+        return 4;
+    };
+    if left == '\n' || right == '\n' {
+        3
+    } else if left.is_whitespace() || right.is_whitespace() {
+        2
+    } else if !left.is_alphanumeric() || !right.is_alphanumeric() {
+        1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff;
+
+    #[test]
+    fn small_equalities_are_replaced() {
+        let chunks = vec![
+            Chunk::Delete("ab".to_string()),
+            Chunk::Insert("xy".to_string()),
+            Chunk::Equal("c".to_string()),
+            Chunk::Delete("de".to_string()),
+            Chunk::Insert("zw".to_string()),
+        ];
+
+        assert_eq!(
+            cleanup(chunks),
+            vec![
+                Chunk::Delete("abcde".to_string()),
+                Chunk::Insert("xyczw".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn insertions_slide_to_word_edges() {
+        let chunks = vec![
+            Chunk::Equal("The c".to_string()),
+            Chunk::Insert("at c".to_string()),
+            Chunk::Equal("ame.".to_string()),
+        ];
+
+        assert_eq!(
+            cleanup(chunks),
+            vec![
+                Chunk::Equal("The ".to_string()),
+                Chunk::Insert("cat ".to_string()),
+                Chunk::Equal("came.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn cleaned_up_script_reaches_target() {
+        for (start, target) in [
+            ("mouse", "sofas"),
+            ("the quick brown fox", "a quack brawn box"),
+            ("kitten", "sitting"),
+        ] {
+            assert_eq!(
+                diff(start, target).cleanup_semantic(start).apply(start),
+                target
+            );
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! A curated subset of the Unicode confusables: groups of chars that look alike in common fonts,
+//! across digits, Latin, Greek and Cyrillic. The chars are hand-picked with the Unicode security
+//! mechanisms' confusables data (UTS #39) as a guide rather than generated from it, favouring
+//! pairs that look alike in monospace terminal fonts.
+
+use std::collections::HashMap;
+
+/// Every group lists chars visually similar to each other. No char is in more than one group.
+const GROUPS: &[&str] = &[
+    "0OoОоΟο",
+    "1lI|ǀӀ",
+    "2Zz",
+    "5Ss$",
+    "9gq",
+    "6b",
+    "aаɑα",
+    "cCсСϲϹ",
+    "eеєε",
+    "iíìïі",
+    "jј",
+    "pрρ",
+    "xXхХχΧ",
+    "yуγ",
+    "AАΑ",
+    "BВΒβ",
+    "EЕΕ",
+    "HНΗ",
+    "KКΚκk",
+    "MМΜ",
+    "NΝ",
+    "PРΡ",
+    "TТΤτ",
+    "YΥ",
+    "uυμ",
+    "vVνѵ",
+    "wWωш",
+    "nηп",
+    "rг",
+    "σς",
+];
+
+/// Pairs of lookalike chars from different groups, or of a char in no group. They link the groups
+/// into chains of gradually changing glyphs without making every char of one group look like
+/// every char of the other.
+const BRIDGES: &[(char, char)] = &[('8', 'B'), ('o', 'σ'), ('σ', 's'), ('ς', 's')];
+
+fn group(c: char) -> Option<&'static str> {
+    GROUPS.iter().copied().find(|group| group.contains(c))
+}
+
+/// Whether `a` and `b` are distinct chars that look alike.
+pub(crate) fn confusable(a: char, b: char) -> bool {
+    a != b
+        && (group(a).is_some_and(|group| group.contains(b))
+            || BRIDGES.contains(&(a, b))
+            || BRIDGES.contains(&(b, a)))
+}
+
+/// The chars that look like `c`, excluding `c` itself.
+fn lookalikes(c: char) -> impl Iterator<Item = char> {
+    let bridged = BRIDGES
+        .iter()
+        .filter(move |&&(a, b)| a == c || b == c)
+        .map(move |&(a, b)| if a == c { b } else { a });
+    group(c)
+        .into_iter()
+        .flat_map(str::chars)
+        .filter(move |&other| other != c)
+        .chain(bridged)
+}
+
+/// The shortest chain of lookalike chars leading from `from` to `to`, excluding both, if there's
+/// one of at most `max_len` chars.
+pub(crate) fn chain(from: char, to: char, max_len: usize) -> Option<Vec<char>> {
+    // Breadth-first search remembering the char every visited one was reached from.
+    let mut reached_from: HashMap<char, char> = HashMap::from([(from, from)]);
+    let mut frontier = vec![from];
+    for _ in 0..=max_len {
+        let mut next = Vec::new();
+        for c in frontier {
+            for lookalike in lookalikes(c) {
+                if reached_from.contains_key(&lookalike) {
+                    continue;
+                }
+                reached_from.insert(lookalike, c);
+                if lookalike == to {
+                    let mut chain = Vec::new();
+                    let mut c = reached_from[&to];
+                    while c != from {
+                        chain.push(c);
+                        c = reached_from[&c];
+                    }
+                    chain.reverse();
+                    return Some(chain);
+                }
+                next.push(lookalike);
+            }
+        }
+        frontier = next;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookalikes_are_confusable() {
+        assert!(confusable('O', '0'));
+        assert!(confusable('o', 'о'));
+        assert!(!confusable('o', 'o'));
+        assert!(!confusable('a', 'b'));
+    }
+
+    #[test]
+    fn chains_lead_through_lookalikes() {
+        assert_eq!(chain('o', 's', 3), Some(vec!['σ']));
+        assert_eq!(chain('O', '0', 3), Some(vec![]));
+        assert_eq!(chain('O', 's', 0), None);
+        assert_eq!(chain('a', 'b', 3), None);
+    }
+
+    #[test]
+    fn groups_are_disjoint() {
+        let mut seen = HashMap::new();
+        for (index, group) in GROUPS.iter().enumerate() {
+            for c in group.chars() {
+                assert_eq!(
+                    *seen.entry(c).or_insert(index),
+                    index,
+                    "{c:?} in several groups"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bridges_link_different_groups() {
+        for &(a, b) in BRIDGES {
+            assert!(
+                group(a).is_none_or(|group| !group.contains(b)),
+                "{a:?} and {b:?} already share a group"
+            );
+        }
+    }
+}
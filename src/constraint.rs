@@ -0,0 +1,685 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    error::Error,
+    fmt,
+    ops::Range,
+};
+
+use crate::{edit::Edit, measure::Measure, schedule::Schedule};
+
+/// A predicate every intermediate value of a morph has to satisfy.
+pub(crate) type Validator = dyn Fn(&str) -> bool + Send + Sync;
+
+/// Edits grouped into the steps a morph advances by, each step applying one or more edits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Plan {
+    pub edits: VecDeque<Edit>,
+    pub steps: VecDeque<usize>,
+}
+
+impl Plan {
+    /// A plan applying one edit per step.
+    pub fn single_steps(edits: VecDeque<Edit>) -> Self {
+        Self {
+            steps: vec![1; edits.len()].into(),
+            edits,
+        }
+    }
+
+    /// A plan applying all edits in a single step.
+    pub fn jump(edits: VecDeque<Edit>) -> Self {
+        Self {
+            steps: (!edits.is_empty())
+                .then_some(edits.len())
+                .into_iter()
+                .collect(),
+            edits,
+        }
+    }
+}
+
+/// Why a morph can't keep within a maximum display width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WidthError {
+    /// The display width of the value that doesn't fit: the target, the current value or one the
+    /// morph can't avoid passing through.
+    pub width: usize,
+    pub max_width: usize,
+}
+
+impl fmt::Display for WidthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the value would be {} columns wide, more than the maximum of {}",
+            self.width, self.max_width
+        )
+    }
+}
+
+impl Error for WidthError {}
+
+/// Reorder and batch the steps of `plan` so that every intermediate value fits within
+/// `max_width` display columns and satisfies `is_valid`.
+///
+/// Steps are greedily taken in the planned order, skipping ahead to the first one that produces a
+/// fitting and valid value. If no single step does, steps are batched until the value fits and is
+/// valid again. The target is never checked by `is_valid`, so in the worst case all the remaining
+/// steps are merged into one going straight to it. That's an error for the width though, as is
+/// the start or the target not fitting.
+///
+/// Widths are tracked per step rather than measured, so the value is only built for `is_valid`.
+pub(crate) fn comply(
+    start: &str,
+    plan: &Plan,
+    max_width: Option<usize>,
+    is_valid: Option<&dyn Fn(&str) -> bool>,
+) -> Result<Plan, WidthError> {
+    let mut reorder = Reorder::new(start, plan);
+    let (mut width, width_deltas) = width_deltas(start, &plan.edits);
+    let fits = |width: usize| max_width.is_none_or(|max_width| width <= max_width);
+    let error = |width| WidthError {
+        width,
+        max_width: max_width.unwrap_or(usize::MAX),
+    };
+    let target_width = width.saturating_add_signed(width_deltas.iter().sum());
+    for width in [width, target_width] {
+        if !fits(width) {
+            return Err(error(width));
+        }
+    }
+
+    let step_delta = |reorder: &Reorder, position| -> isize {
+        reorder
+            .unit(position)
+            .iter()
+            .map(|&id| width_deltas[id])
+            .sum()
+    };
+
+    while !reorder.remaining.is_empty() {
+        let last = reorder.remaining.len() == 1;
+        // Slots of the steps passed over, which the later steps touching them have to wait for.
+        let mut earlier_slots = HashSet::new();
+        let single = (0..reorder.remaining.len()).find(|&position| {
+            reorder.is_ready_after(position, &mut earlier_slots)
+                && fits(width.saturating_add_signed(step_delta(&reorder, position)))
+                && (last || is_valid.is_none_or(|is_valid| is_valid(&reorder.preview(position))))
+        });
+
+        if let Some(position) = single {
+            width = width.saturating_add_signed(step_delta(&reorder, position));
+            reorder.apply(&[position]);
+            continue;
+        }
+
+        // No single step keeps the value fitting and valid. Batch steps in the planned order until
+        // one does, noting whether any batch short of the target fits at all. The batched value
+        // is built up step by step on the schedule and undone before the batch is applied.
+        let mut batched = reorder.current.clone();
+        reorder.preview_onto(0, &mut batched);
+        let mut batch_len = 1;
+        let mut batched_width = width.saturating_add_signed(step_delta(&reorder, 0));
+        let mut narrowest = batched_width;
+        while batch_len < reorder.remaining.len()
+            && !(fits(batched_width) && is_valid.is_none_or(|is_valid| is_valid(&batched)))
+        {
+            reorder.preview_onto(batch_len, &mut batched);
+            batched_width = batched_width.saturating_add_signed(step_delta(&reorder, batch_len));
+            batch_len += 1;
+            if batch_len < reorder.remaining.len() {
+                narrowest = narrowest.min(batched_width);
+            }
+        }
+        reorder.undo(0..batch_len);
+        if batch_len > 1 && batch_len == reorder.remaining.len() && !fits(narrowest) {
+            return Err(error(narrowest));
+        }
+        width = batched_width;
+        reorder.apply(&(0..batch_len).collect::<Vec<_>>());
+    }
+
+    Ok(reorder.finish())
+}
+
+/// The display width of `start` and how much every edit of `edits` changes it. Edits touching the
+/// same char keep their relative order however the steps get reordered, so they always replace
+/// the same chars and the deltas hold for any order.
+fn width_deltas(start: &str, edits: &VecDeque<Edit>) -> (usize, Vec<isize>) {
+    let mut chars: Vec<char> = start.chars().collect();
+    let mut measure = Measure::of(start);
+    let start_width = measure.width;
+
+    let deltas = edits
+        .iter()
+        .map(|edit| {
+            let removed = match *edit {
+                Edit::Insert { c, index } => {
+                    chars.insert(index, c);
+                    None
+                }
+                Edit::Delete { index } => Some(chars.remove(index)),
+                Edit::Substitute { c, index } => Some(std::mem::replace(&mut chars[index], c)),
+            };
+            let before = measure.width as isize;
+            measure.update(edit, removed);
+            measure.width as isize - before
+        })
+        .collect();
+
+    (start_width, deltas)
+}
+
+/// Reorder and batch the steps of `plan` so that the length of the value only ever moves towards
+/// the length of the target, i.e. never overshoots it or moves away from it.
+///
+/// Steps that would move the length the wrong way are batched with the next steps that
+/// compensate for them, so e.g. with the target longer than the start every deletion is applied
+/// together with an insertion.
+pub(crate) fn monotonic_length(start: &str, plan: Plan) -> Plan {
+    let mut reorder = Reorder::new(start, &plan);
+    let mut len = start.chars().count() as isize;
+    let target_len = len
+        + (0..reorder.remaining.len())
+            .map(|position| reorder.length_delta(position))
+            .sum::<isize>();
+
+    while !reorder.remaining.is_empty() {
+        let mut batch = vec![0];
+        let mut delta = reorder.length_delta(0);
+
+        while !is_towards(len, len + delta, target_len) {
+            // Look for the first ready step compensating for the wrong direction. Fall back to the
+            // next step in the planned order.
+            let unbatched =
+                || (1..reorder.remaining.len()).filter(|position| !batch.contains(position));
+            let compensating = unbatched().find(|&position| {
+                reorder.is_ready(position, &batch)
+                    && reorder.length_delta(position).signum() == -delta.signum()
+            });
+
+            let Some(next) = compensating.or_else(|| unbatched().next()) else {
+                break;
+            };
+            delta += reorder.length_delta(next);
+            batch.push(next);
+        }
+
+        len += delta;
+        reorder.apply(&batch);
+    }
+
+    reorder.finish()
+}
+
+/// Batch steps of `plan` inserting a char with ones deleting a char, and vice versa, so the chars
+/// after both stay in place rather than shifting back and forth. Every step is batched with the
+/// closest compensating one, minimizing the chars between them, which still shift once.
+pub(crate) fn minimize_jitter(start: &str, plan: Plan) -> Plan {
+    let mut reorder = Reorder::new(start, &plan);
+
+    while !reorder.remaining.is_empty() {
+        let delta = reorder.length_delta(0);
+        let slot = reorder.slot(0);
+        let compensating = (1..reorder.remaining.len())
+            .filter(|&position| {
+                delta != 0
+                    && reorder.length_delta(position) == -delta
+                    && reorder.is_ready(position, &[0])
+            })
+            .min_by_key(|&position| reorder.slot(position).abs_diff(slot));
+
+        match compensating {
+            Some(position) => reorder.apply(&[0, position]),
+            None => reorder.apply(&[0]),
+        }
+    }
+
+    reorder.finish()
+}
+
+/// Reorder the steps of `plan` so that deletions, insertions and substitutions are spread evenly
+/// across the morph, each kind progressing in proportion to how many steps of it there are, which
+/// makes the old value appear to crossfade into the new one.
+///
+/// The next step is the first ready one of the kind furthest behind, or the next planned step if
+/// no step of that kind can go ahead of the steps touching the same char.
+pub(crate) fn crossfade(start: &str, plan: Plan) -> Plan {
+    let mut reorder = Reorder::new(start, &plan);
+    // Steps of every kind, by the sign of their length delta plus one, taken and in total.
+    let kind = |reorder: &Reorder, position| (reorder.length_delta(position).signum() + 1) as usize;
+    let mut taken = [0; 3];
+    let mut totals = [0; 3];
+    for position in 0..reorder.remaining.len() {
+        totals[kind(&reorder, position)] += 1;
+    }
+
+    while !reorder.remaining.is_empty() {
+        // The kind whose middle of its next step is the least far along, i.e. with the smallest
+        // (2 * taken + 1) / (2 * total).
+        let behind = (0..3)
+            .filter(|&k| taken[k] < totals[k])
+            .min_by(|&a, &b| {
+                ((2 * taken[a] + 1) * totals[b]).cmp(&((2 * taken[b] + 1) * totals[a]))
+            })
+            .expect("a kind has steps remaining");
+        let position = (0..reorder.remaining.len())
+            .find(|&position| kind(&reorder, position) == behind && reorder.is_ready(position, &[]))
+            .unwrap_or(0);
+
+        taken[kind(&reorder, position)] += 1;
+        reorder.apply(&[position]);
+    }
+
+    reorder.finish()
+}
+
+/// Reorder the steps of `plan` so that those editing chars within `viewport`, a range of char
+/// indexes of the evolving value, go first. Other steps keep their planned order and are only
+/// taken when no step within the viewport can go ahead of the steps touching the same char.
+pub(crate) fn prioritize_viewport(start: &str, plan: Plan, viewport: &Range<usize>) -> Plan {
+    let mut reorder = Reorder::new(start, &plan);
+
+    while !reorder.remaining.is_empty() {
+        let visible = (0..reorder.remaining.len()).find(|&position| {
+            viewport.contains(&reorder.index(position)) && reorder.is_ready(position, &[])
+        });
+        reorder.apply(&[visible.unwrap_or(0)]);
+    }
+
+    reorder.finish()
+}
+
+/// Merge consecutive steps of `plan` touching the same word into a single step, so that every
+/// step completes a whole word. Words are separated by chars that are whitespace in the target or
+/// whitespace that gets deleted. Edits of the separators belong to the word before them.
+pub(crate) fn group_words(start: &str, plan: Plan) -> Plan {
+    group(start, plan, char::is_whitespace)
+}
+
+/// Like [`group_words`] but for lines, separated by newlines.
+pub(crate) fn group_lines(start: &str, plan: Plan) -> Plan {
+    group(start, plan, |c| c == '\n')
+}
+
+fn group(start: &str, plan: Plan, is_separator: fn(char) -> bool) -> Plan {
+    let schedule = Schedule::new(start, &plan.edits);
+
+    let mut words = Vec::with_capacity(schedule.slot_count());
+    let mut word = 0;
+    for slot in 0..schedule.slot_count() {
+        words.push(word);
+        if is_separator(schedule.last_char(slot)) {
+            word += 1;
+        }
+    }
+
+    let mut steps: VecDeque<usize> = VecDeque::with_capacity(plan.steps.len());
+    let mut previous_word = None;
+    let mut id = 0;
+    for step in plan.steps {
+        let word = words[schedule.slot(id)];
+        match steps.back_mut() {
+            Some(last) if previous_word == Some(word) => *last += step,
+            _ => steps.push_back(step),
+        }
+        previous_word = Some(word);
+        id += step;
+    }
+
+    Plan {
+        edits: plan.edits,
+        steps,
+    }
+}
+
+/// Whether moving from length `from` to `to` stays between `from` and `target` (inclusive).
+fn is_towards(from: isize, to: isize, target: isize) -> bool {
+    from.min(target) <= to && to <= from.max(target)
+}
+
+/// Bookkeeping shared by the constraints: the steps of a plan as units of edit ids that can be
+/// applied in a different order, or several at once, via a [`Schedule`].
+struct Reorder {
+    schedule: Schedule,
+    edits: VecDeque<Edit>,
+    // Edit ids of every step of the original plan.
+    units: Vec<Vec<usize>>,
+    // Indexes into `units` not applied yet, in the planned order. The constraints refer to these
+    // by their position in this list.
+    remaining: Vec<usize>,
+    current: String,
+    planned: Plan,
+}
+
+impl Reorder {
+    fn new(start: &str, plan: &Plan) -> Self {
+        let mut units = Vec::with_capacity(plan.steps.len());
+        let mut next_id = 0;
+        for step in &plan.steps {
+            units.push((next_id..next_id + step).collect());
+            next_id += step;
+        }
+
+        Self {
+            schedule: Schedule::new(start, &plan.edits),
+            edits: plan.edits.clone(),
+            remaining: (0..units.len()).collect(),
+            units,
+            current: start.to_string(),
+            planned: Plan {
+                edits: VecDeque::with_capacity(plan.edits.len()),
+                steps: VecDeque::with_capacity(plan.steps.len()),
+            },
+        }
+    }
+
+    fn unit(&self, position: usize) -> &[usize] {
+        &self.units[self.remaining[position]]
+    }
+
+    /// Whether the remaining step at `position` can be applied before the steps planned before
+    /// it, except for those at `batched` which get applied with it, without breaking the order of
+    /// edits touching the same char.
+    fn is_ready(&self, position: usize, batched: &[usize]) -> bool {
+        (0..position)
+            .filter(|earlier| !batched.contains(earlier))
+            .all(|earlier| {
+                self.unit(earlier).iter().all(|&earlier| {
+                    self.unit(position)
+                        .iter()
+                        .all(|&id| self.schedule.slot(earlier) != self.schedule.slot(id))
+                })
+            })
+    }
+
+    /// Like [`Reorder::is_ready`] with no steps batched, given `earlier_slots`, the chars the steps
+    /// before `position` touch, which the step's own chars get added to for the next position.
+    fn is_ready_after(&self, position: usize, earlier_slots: &mut HashSet<usize>) -> bool {
+        let unit = self.unit(position);
+        let ready = unit
+            .iter()
+            .all(|&id| !earlier_slots.contains(&self.schedule.slot(id)));
+        earlier_slots.extend(unit.iter().map(|&id| self.schedule.slot(id)));
+        ready
+    }
+
+    /// The char the first edit of the remaining step at `position` touches. See [`Schedule`].
+    fn slot(&self, position: usize) -> usize {
+        self.schedule.slot(self.unit(position)[0])
+    }
+
+    /// The index in the current value the first edit of the remaining step at `position` applies
+    /// to.
+    fn index(&self, position: usize) -> usize {
+        self.schedule.resolve(self.unit(position)[0]).index()
+    }
+
+    /// How much the remaining step at `position` changes the length of the value.
+    fn length_delta(&self, position: usize) -> isize {
+        self.unit(position)
+            .iter()
+            .map(|&id| match self.edits[id] {
+                Edit::Insert { .. } => 1,
+                Edit::Delete { .. } => -1,
+                Edit::Substitute { .. } => 0,
+            })
+            .sum()
+    }
+
+    /// The value after applying the ready remaining step at `position`.
+    fn preview(&mut self, position: usize) -> String {
+        let mut value = self.current.clone();
+        self.preview_onto(position, &mut value);
+        self.undo(position..position + 1);
+        value
+    }
+
+    /// Apply the remaining step at `position` to `value` and the schedule but not the plan, to be
+    /// undone with [`Reorder::undo`]. The step must be ready given the steps previewed before it.
+    fn preview_onto(&mut self, position: usize, value: &mut String) {
+        for &id in &self.units[self.remaining[position]] {
+            self.schedule.apply(id).apply_in_place(value);
+        }
+    }
+
+    /// Undo the previews of the remaining steps at `positions`, in the order they were previewed.
+    fn undo(&mut self, positions: Range<usize>) {
+        for position in positions.rev() {
+            for &id in self.units[self.remaining[position]].iter().rev() {
+                self.schedule.undo(id);
+            }
+        }
+    }
+
+    /// Apply the remaining steps at `positions`, in the given order, as a single step.
+    fn apply(&mut self, positions: &[usize]) {
+        let mut step = 0;
+        for &position in positions {
+            for &id in &self.units[self.remaining[position]] {
+                let edit = self.schedule.apply(id);
+                edit.apply_in_place(&mut self.current);
+                self.planned.edits.push_back(edit);
+                step += 1;
+            }
+        }
+        self.planned.steps.push_back(step);
+
+        let mut positions = positions.to_vec();
+        positions.sort_unstable();
+        for position in positions.into_iter().rev() {
+            self.remaining.remove(position);
+        }
+    }
+
+    fn finish(self) -> Plan {
+        self.planned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{levenshtein::compute_edit_sequence, measure::display_width};
+
+    fn frames(start: &str, plan: &Plan) -> Vec<String> {
+        let mut edits = plan.edits.iter();
+        let mut frames = vec![start.to_string()];
+        for step in &plan.steps {
+            let mut frame = frames.last().expect("non-empty").clone();
+            for edit in edits.by_ref().take(*step) {
+                frame = edit.apply(&frame);
+            }
+            frames.push(frame);
+        }
+        frames
+    }
+
+    fn plan(start: &str, target: &str) -> Plan {
+        Plan::single_steps(compute_edit_sequence(start, target))
+    }
+
+    fn valid_plan(start: &str, plan: Plan, is_valid: impl Fn(&str) -> bool) -> Plan {
+        let is_valid: &dyn Fn(&str) -> bool = &is_valid;
+        comply(start, &plan, None, Some(is_valid)).expect("there's no maximum width")
+    }
+
+    #[test]
+    fn valid_plans_are_kept() {
+        let plan = plan("kitten", "mittens");
+
+        assert_eq!(valid_plan("kitten", plan.clone(), |_: &str| true), plan);
+    }
+
+    #[test]
+    fn edits_are_reordered_to_avoid_forbidden_values() {
+        // Planned order would pass through "hat".
+        let is_valid = |value: &str| value != "hat";
+
+        let complying = valid_plan("cat", plan("cat", "hut"), is_valid);
+
+        assert_eq!(frames("cat", &complying), vec!["cat", "cut", "hut"]);
+    }
+
+    #[test]
+    fn edits_are_batched_when_reordering_is_not_enough() {
+        let is_valid = |value: &str| value == "ab" || value == "cd";
+
+        let complying = valid_plan("ab", plan("ab", "cd"), is_valid);
+
+        assert_eq!(complying.steps, VecDeque::from([2]));
+        assert_eq!(frames("ab", &complying), vec!["ab", "cd"]);
+    }
+
+    #[test]
+    fn scrambled_edits_of_one_char_stay_in_order() {
+        let plan = Plan::single_steps(VecDeque::from([
+            Edit::Insert { c: 'x', index: 0 },
+            Edit::Substitute { c: 'y', index: 0 },
+            Edit::Substitute { c: 'b', index: 1 },
+        ]));
+        // The substitution of 'b' has to go first but 'y' can't be substituted before 'x' is
+        // inserted, so the two are batched.
+        let is_valid = |value: &str| !value.starts_with('x');
+
+        let complying = valid_plan("a", plan, is_valid);
+
+        assert_eq!(frames("a", &complying), vec!["a", "b", "yb"]);
+    }
+
+    #[test]
+    fn widths_are_tracked_per_step() {
+        // Deleting the narrow chars first keeps within the width of the start.
+        let complying = comply("abc", &plan("abc", "你x"), Some(3), None).unwrap();
+
+        for frame in frames("abc", &complying) {
+            assert!(display_width(&frame) <= 3, "{frame}");
+        }
+    }
+
+    #[test]
+    fn unavoidable_widths_are_errors() {
+        let too_wide = |width, max_width| Err(WidthError { width, max_width });
+        assert_eq!(
+            comply("abc", &plan("abc", "a"), Some(2), None),
+            too_wide(3, 2)
+        );
+        assert_eq!(
+            comply("a", &plan("a", "abc"), Some(2), None),
+            too_wide(3, 2)
+        );
+
+        // The second substitution can't go before the first one, which is too wide.
+        let plan = Plan::single_steps(VecDeque::from([
+            Edit::Substitute { c: '你', index: 0 },
+            Edit::Substitute { c: 'b', index: 0 },
+        ]));
+        assert_eq!(comply("a", &plan, Some(1), None), too_wide(2, 1));
+    }
+
+    #[test]
+    fn length_moves_monotonically_towards_target() {
+        let pairs = [
+            ("abcdef", "xazced"),
+            ("sunday", "saturday"),
+            ("saturday", "sunday"),
+            (
+                "daskdas dasd sadjasnd dsdjfh",
+                "nfad ad f sasdkmfpsmdfasM Ksmdnfkdsk",
+            ),
+            (
+                "nfad ad f sasdkmfpsmdfasM Ksmdnfkdsk",
+                "daskdas dasd sadjasnd dsdjfh",
+            ),
+        ];
+
+        for (start, target) in pairs {
+            let monotonic = monotonic_length(start, plan(start, target));
+            let lengths: Vec<_> = frames(start, &monotonic)
+                .iter()
+                .map(|frame| frame.chars().count())
+                .collect();
+
+            assert_eq!(lengths.last(), Some(&target.chars().count()));
+            if start.len() <= target.len() {
+                assert!(lengths.is_sorted(), "{start} -> {target}: {lengths:?}");
+            } else {
+                assert!(
+                    lengths.iter().rev().is_sorted(),
+                    "{start} -> {target}: {lengths:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tail_stays_in_place() {
+        let steady = minimize_jitter("xabcde", plan("xabcde", "abycdez"));
+
+        // Deleting x is batched with inserting the closest y.
+        assert_eq!(
+            frames("xabcde", &steady),
+            vec!["xabcde", "abycde", "abycdez"]
+        );
+    }
+
+    #[test]
+    fn deletions_and_insertions_interleave() {
+        let mut edits: VecDeque<Edit> = std::iter::repeat_n(Edit::Delete { index: 0 }, 4).collect();
+        edits.extend(
+            "wxyz"
+                .chars()
+                .enumerate()
+                .map(|(index, c)| Edit::Insert { c, index }),
+        );
+
+        let crossfading = crossfade("abcd", Plan::single_steps(edits));
+
+        assert_eq!(
+            frames("abcd", &crossfading),
+            vec![
+                "abcd", "bcd", "bcdw", "cdw", "cdwx", "dwx", "dwxy", "wxy", "wxyz"
+            ]
+        );
+    }
+
+    #[test]
+    fn edits_within_the_viewport_go_first() {
+        let start = "aaaa bbbb cccc";
+        let prioritized = prioritize_viewport(start, plan(start, "xxxx bbbb yyyy"), &(10..14));
+
+        assert_eq!(frames(start, &prioritized)[4], "aaaa bbbb yyyy");
+    }
+
+    #[test]
+    fn steps_complete_whole_words() {
+        let grouped = group_words("the quick fox", plan("the quick fox", "a quack box"));
+
+        assert_eq!(
+            frames("the quick fox", &grouped),
+            vec!["the quick fox", "a quick fox", "a quack fox", "a quack box"]
+        );
+    }
+
+    #[test]
+    fn steps_complete_whole_lines() {
+        let grouped = group_lines("ab\ncd", plan("ab\ncd", "xy\nzw"));
+
+        assert_eq!(
+            frames("ab\ncd", &grouped),
+            vec!["ab\ncd", "xy\ncd", "xy\nzw"]
+        );
+    }
+
+    #[test]
+    fn constraints_compose() {
+        let monotonic = monotonic_length("abcdef", plan("abcdef", "xazced"));
+        let is_valid = |value: &str| !value.starts_with('x');
+
+        let complying = valid_plan("abcdef", monotonic, is_valid);
+        let frames = frames("abcdef", &complying);
+
+        assert_eq!(frames.last().map(String::as_str), Some("xazced"));
+        assert!(frames.iter().all(|frame| frame.chars().count() == 6));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{MorphingString, Progress, edit::Edit};
+
+/// A [`MorphingString`] edited by a simulated cursor that travels one char per advance to where
+/// the next edit is, like someone editing the line with the arrow keys.
+///
+/// Advances that only move the cursor leave the value as is.
+pub struct CursorMorphingString {
+    morph: MorphingString,
+    // The index of the char the cursor is in front of.
+    cursor: usize,
+}
+
+impl CursorMorphingString {
+    /// Wrap `morph` with the cursor at the end of its value.
+    pub fn new(morph: MorphingString) -> Self {
+        let cursor = morph.value().chars().count();
+        Self { morph, cursor }
+    }
+
+    pub fn set_target(&mut self, target: String) {
+        self.morph.set_target(target);
+    }
+
+    /// Move the cursor one char towards the next edit or, if it's there already, apply the next
+    /// step of edits.
+    pub fn advance(&mut self) -> Progress {
+        let Some(next) = self.morph.remaining_edits.first() else {
+            return self.morph.advance();
+        };
+        let site = next.index();
+        if self.cursor < site {
+            self.cursor += 1;
+            return self.morph.progress();
+        }
+        if self.cursor > site {
+            self.cursor -= 1;
+            return self.morph.progress();
+        }
+
+        let progress = self.morph.advance();
+        // Typing moves the cursor past the typed char, deleting leaves it in place.
+        if let Some(edit) = self.morph.last_edits().last() {
+            self.cursor = match *edit {
+                Edit::Insert { index, .. } | Edit::Substitute { index, .. } => index + 1,
+                Edit::Delete { index } => index,
+            };
+        }
+        progress
+    }
+
+    /// The index of the char the cursor is in front of, equal to the number of chars when it's at
+    /// the end of the value.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn morph(&self) -> &MorphingString {
+        &self.morph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_travels_between_edits() {
+        let mut string = CursorMorphingString::new(MorphingString::new("abcd".to_string()));
+        string.set_target("xbcd".to_string());
+
+        let mut frames = vec![(string.morph().value().to_string(), string.cursor())];
+        while !string.advance().is_complete() {
+            frames.push((string.morph().value().to_string(), string.cursor()));
+        }
+        frames.push((string.morph().value().to_string(), string.cursor()));
+
+        let expected = [
+            ("abcd", 4),
+            ("abcd", 3),
+            ("abcd", 2),
+            ("abcd", 1),
+            ("abcd", 0),
+            ("xbcd", 1),
+        ];
+        assert_eq!(
+            frames,
+            expected.map(|(value, cursor)| (value.to_string(), cursor))
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{
+    MorphingString,
+    chunk::{self, Chunk},
+    edit::Edit,
+    script::EditScript,
+};
+
+/// Longer texts are described by their length rather than quoted.
+const MAX_QUOTED: usize = 10;
+
+impl EditScript {
+    /// A human-readable summary of what the script changes in `start`, e.g. `replaces 'k' with 's'
+    /// at start, inserts 'g' at end`, for announcing morphs to screen readers instead of every
+    /// frame.
+    pub fn describe(&self, start: &str) -> String {
+        describe(start, self.iter())
+    }
+}
+
+impl MorphingString {
+    /// A human-readable summary of the remaining edits of the current morph. See
+    /// [`EditScript::describe`].
+    pub fn describe(&self) -> String {
+        describe(
+            &self.current_value,
+            self.remaining_edits.iter().map(|edit| edit.unpack()),
+        )
+    }
+}
+
+fn describe(start: &str, edits: impl IntoIterator<Item = Edit>) -> String {
+    let chunks = chunk::chunks(start, edits);
+    let mut changes = Vec::new();
+    // The index in `start` of the next chunk.
+    let mut index = 0;
+    let mut i = 0;
+
+    while i < chunks.len() {
+        if let Chunk::Equal(text) = &chunks[i] {
+            index += text.chars().count();
+            i += 1;
+            continue;
+        }
+
+        let at = index;
+        let (mut deleted, mut inserted) = ("", "");
+        while let Some(chunk @ (Chunk::Delete(_) | Chunk::Insert(_))) = chunks.get(i) {
+            match chunk {
+                Chunk::Delete(text) => {
+                    deleted = text;
+                    index += text.chars().count();
+                }
+                Chunk::Insert(text) => inserted = text,
+                Chunk::Equal(_) => unreachable!(),
+            }
+            i += 1;
+        }
+
+        let change = match (deleted.is_empty(), inserted.is_empty()) {
+            (false, false) => format!("replaces {} with {}", quote(deleted), quote(inserted)),
+            (false, true) => format!("deletes {}", quote(deleted)),
+            _ => format!("inserts {}", quote(inserted)),
+        };
+        let position = match (at == 0, i == chunks.len()) {
+            (true, true) => String::new(),
+            (true, false) => " at start".to_string(),
+            (false, true) => " at end".to_string(),
+            (false, false) => format!(" at position {at}"),
+        };
+        changes.push(change + &position);
+    }
+
+    if changes.is_empty() {
+        return "no changes".to_string();
+    }
+    changes.join(", ")
+}
+
+fn quote(text: &str) -> String {
+    match text.chars().count() {
+        len if len <= MAX_QUOTED => format!("'{text}'"),
+        len => format!("{len} characters"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MorphingString, diff};
+
+    #[test]
+    fn changes_are_summarized() {
+        assert_eq!(
+            diff("kitten", "sitting").describe("kitten"),
+            "replaces 'k' with 's' at start, replaces 'e' with 'i' at position 4, inserts 'g' at end"
+        );
+        assert_eq!(
+            diff("ab", "xyz xyz xyz").describe("ab"),
+            "replaces 'ab' with 11 characters"
+        );
+        assert_eq!(diff("ab", "ab").describe("ab"), "no changes");
+    }
+
+    #[test]
+    fn morph_describes_the_remaining_edits() {
+        let mut morph = MorphingString::new("walk".to_string());
+        morph.set_target("walking".to_string());
+        morph.advance();
+
+        assert_eq!(morph.describe(), "inserts 'ng' at end");
+    }
+}
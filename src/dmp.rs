@@ -0,0 +1,336 @@
+//! Conversion of [`EditScript`]s to and from the patch text format of diff-match-patch.
+//!
+//! Positions and lengths count chars, like the Python implementation of diff-match-patch.
+
+use std::{error::Error, fmt};
+
+use crate::{
+    chunk::{self, Chunk},
+    script::EditScript,
+};
+
+/// The number of unchanged chars of context around every patch.
+const MARGIN: usize = 4;
+
+/// Why patch text couldn't be turned into an [`EditScript`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    /// The line, counted from 1, isn't a valid header or diff line.
+    Syntax { line: usize },
+    /// The context or deleted text at the line, counted from 1, doesn't match the start string.
+    Mismatch { line: usize },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::Syntax { line } => write!(f, "invalid patch syntax on line {line}"),
+            PatchError::Mismatch { line } => {
+                write!(f, "patch doesn't match the start string on line {line}")
+            }
+        }
+    }
+}
+
+impl Error for PatchError {}
+
+impl EditScript {
+    /// Render the edits applied to `start` as diff-match-patch patch text.
+    pub fn to_dmp_patch(&self, start: &str) -> String {
+        patches(&chunk::chunks(start, self.iter()))
+            .iter()
+            .map(Patch::to_string)
+            .collect()
+    }
+
+    /// Parse diff-match-patch patch text into a script applying it to `start`. Unlike
+    /// diff-match-patch, the patches have to apply exactly.
+    pub fn from_dmp_patch(patch: &str, start: &str) -> Result<EditScript, PatchError> {
+        let start: Vec<char> = start.chars().collect();
+        let mut chunks = Vec::new();
+        // The position in `start` everything before has been turned into chunks up to.
+        let mut cursor = 0;
+        // How much longer the patches so far made the text.
+        let mut shift = 0;
+
+        for patch in parse(patch)? {
+            let position = (patch.start1 as isize - shift)
+                .try_into()
+                .ok()
+                .filter(|&position| position >= cursor && position <= start.len())
+                .ok_or(PatchError::Mismatch { line: patch.line })?;
+            chunk::push(
+                &mut chunks,
+                Chunk::Equal(start[cursor..position].iter().collect()),
+            );
+            cursor = position;
+
+            for (offset, diff) in patch.diffs.into_iter().enumerate() {
+                if let Chunk::Equal(text) | Chunk::Delete(text) = &diff {
+                    let len = text.chars().count();
+                    let matches = start.get(cursor..cursor + len).is_some_and(|chars| {
+                        chars.iter().eq(text.chars().collect::<Vec<_>>().iter())
+                    });
+                    if !matches {
+                        return Err(PatchError::Mismatch {
+                            line: patch.line + 1 + offset,
+                        });
+                    }
+                    cursor += len;
+                }
+                chunk::push(&mut chunks, diff);
+            }
+            shift += patch.length2 as isize - patch.length1 as isize;
+        }
+        chunk::push(&mut chunks, Chunk::Equal(start[cursor..].iter().collect()));
+
+        Ok(chunk::edits(&chunks).into())
+    }
+}
+
+struct Patch {
+    // Positions in the text with all the previous patches applied.
+    start1: usize,
+    start2: usize,
+    length1: usize,
+    length2: usize,
+    diffs: Vec<Chunk>,
+    // The line of the header, counted from 1, when parsed.
+    line: usize,
+}
+
+impl Patch {
+    fn new(position: usize) -> Self {
+        Self {
+            start1: position,
+            start2: position,
+            length1: 0,
+            length2: 0,
+            diffs: Vec::new(),
+            line: 0,
+        }
+    }
+
+    fn push(&mut self, diff: Chunk) {
+        let len = diff.text().chars().count();
+        match diff {
+            Chunk::Equal(_) => {
+                self.length1 += len;
+                self.length2 += len;
+            }
+            Chunk::Delete(_) => self.length1 += len,
+            Chunk::Insert(_) => self.length2 += len,
+        }
+        chunk::push(&mut self.diffs, diff);
+    }
+}
+
+/// Group changes into patches with some unchanged context around them. Changes close to each
+/// other share a patch.
+fn patches(chunks: &[Chunk]) -> Vec<Patch> {
+    let mut patches = Vec::new();
+    let mut patch: Option<Patch> = None;
+    // The position in the text with all the previous changes applied.
+    let mut position = 0;
+
+    for (index, diff) in chunks.iter().enumerate() {
+        let len = diff.text().chars().count();
+        match diff {
+            Chunk::Equal(text) => {
+                if let Some(mut current) = patch.take() {
+                    if len <= 2 * MARGIN && index + 1 < chunks.len() {
+                        current.push(diff.clone());
+                        patch = Some(current);
+                    } else {
+                        current.push(Chunk::Equal(text.chars().take(MARGIN).collect()));
+                        patches.push(current);
+                    }
+                }
+                position += len;
+            }
+            Chunk::Delete(_) | Chunk::Insert(_) => {
+                let current = patch.get_or_insert_with(|| {
+                    let prefix: Vec<char> = match index.checked_sub(1).map(|index| &chunks[index]) {
+                        Some(Chunk::Equal(text)) => text.chars().rev().take(MARGIN).collect(),
+                        _ => Vec::new(),
+                    };
+                    let mut patch = Patch::new(position - prefix.len());
+                    patch.push(Chunk::Equal(prefix.into_iter().rev().collect()));
+                    patch
+                });
+                current.push(diff.clone());
+                if let Chunk::Insert(_) = diff {
+                    position += len;
+                }
+            }
+        }
+    }
+
+    patches.extend(patch);
+    patches
+}
+
+impl fmt::Display for Patch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coordinates = |start: usize, len: usize| match len {
+            0 => format!("{start},0"),
+            1 => format!("{}", start + 1),
+            _ => format!("{},{len}", start + 1),
+        };
+        writeln!(
+            f,
+            "@@ -{} +{} @@",
+            coordinates(self.start1, self.length1),
+            coordinates(self.start2, self.length2)
+        )?;
+
+        for diff in &self.diffs {
+            let sign = match diff {
+                Chunk::Equal(_) => ' ',
+                Chunk::Delete(_) => '-',
+                Chunk::Insert(_) => '+',
+            };
+            writeln!(f, "{sign}{}", encode(diff.text()))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse(text: &str) -> Result<Vec<Patch>, PatchError> {
+    let mut patches: Vec<Patch> = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let syntax = PatchError::Syntax { line: line_number };
+
+        if let Some(header) = line.strip_prefix("@@ -") {
+            let (old, new) = header
+                .strip_suffix(" @@")
+                .and_then(|header| header.split_once(" +"))
+                .ok_or(syntax.clone())?;
+            let (start1, length1) = parse_coordinates(old).ok_or(syntax.clone())?;
+            let (start2, length2) = parse_coordinates(new).ok_or(syntax)?;
+            patches.push(Patch {
+                start1,
+                start2,
+                length1,
+                length2,
+                diffs: Vec::new(),
+                line: line_number,
+            });
+            continue;
+        }
+
+        let Some(patch) = patches.last_mut() else {
+            return Err(syntax);
+        };
+        let mut chars = line.chars();
+        let sign = chars.next();
+        let text = || decode(chars.as_str()).ok_or(syntax.clone());
+        match sign {
+            Some(' ') => patch.diffs.push(Chunk::Equal(text()?)),
+            Some('-') => patch.diffs.push(Chunk::Delete(text()?)),
+            Some('+') => patch.diffs.push(Chunk::Insert(text()?)),
+            None => {}
+            Some(_) => return Err(syntax),
+        }
+    }
+
+    Ok(patches)
+}
+
+/// Parse `start,length` or `start` coordinates of a header into a zero-based start and a length.
+fn parse_coordinates(coordinates: &str) -> Option<(usize, usize)> {
+    match coordinates.split_once(',') {
+        None => Some((coordinates.parse::<usize>().ok()?.checked_sub(1)?, 1)),
+        Some((start, "0")) => Some((start.parse().ok()?, 0)),
+        Some((start, length)) => Some((
+            start.parse::<usize>().ok()?.checked_sub(1)?,
+            length.parse().ok()?,
+        )),
+    }
+}
+
+/// Percent-encode `text` like diff-match-patch does, i.e. like JavaScript's `encodeURI` with
+/// spaces and a few more chars left as they are.
+fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || " -_.!~*'();/?:@&=+$,#".contains(c) {
+            encoded.push(c);
+        } else {
+            let mut buffer = [0; 4];
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                encoded.push_str(&format!("%{byte:02X}"));
+            }
+        }
+    }
+    encoded
+}
+
+fn decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff;
+
+    #[test]
+    fn renders_patch_text() {
+        let start = "The quick brown fox jumps over the lazy dog.";
+        let target = "That quick brown fox jumped over a lazy dog.";
+
+        assert_eq!(
+            diff(start, target).to_dmp_patch(start),
+            "@@ -1,7 +1,8 @@\n Th\n-e\n+at\n  qui\n\
+             @@ -22,18 +22,17 @@\n jump\n-s\n+ed\n  over \n-the\n+a\n  laz\n"
+        );
+    }
+
+    #[test]
+    fn patch_text_round_trips() {
+        for (start, target) in [
+            (
+                "The quick brown fox jumps over the lazy dog.",
+                "That quick brown fox jumped over a lazy dog.",
+            ),
+            ("kočka\nčerná", "kotě\nbílé 100%"),
+            ("", "abc"),
+            ("abc", ""),
+        ] {
+            let patch = diff(start, target).to_dmp_patch(start);
+            let script = EditScript::from_dmp_patch(&patch, start).unwrap();
+
+            assert_eq!(script.apply(start), target, "{patch}");
+        }
+    }
+
+    #[test]
+    fn mismatching_patch_is_rejected() {
+        let patch = "@@ -1,3 +1,3 @@\n a\n-b\n+x\n c\n";
+
+        assert!(EditScript::from_dmp_patch(patch, "abc").is_ok());
+        assert_eq!(
+            EditScript::from_dmp_patch(patch, "axc"),
+            Err(PatchError::Mismatch { line: 3 })
+        );
+        assert_eq!(
+            EditScript::from_dmp_patch("@@ -1 +1 @@\n?a\n", "a"),
+            Err(PatchError::Syntax { line: 2 })
+        );
+    }
+}
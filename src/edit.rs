@@ -1,5 +1,12 @@
+use std::{fmt, num::TryFromIntError, ops::Deref};
+
+use smallstr::SmallString;
+use smallvec::Array;
+
+/// A single character edit. Indexes are char (not byte) indexes into the string the edit is
+/// applied to.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum Edit {
+pub enum Edit {
     Insert { c: char, index: usize },
     Delete { index: usize },
     Substitute { c: char, index: usize },
@@ -7,21 +14,196 @@ pub(crate) enum Edit {
 
 impl Edit {
     pub fn apply(&self, string: &str) -> String {
-        let mut chars: Vec<char> = string.chars().collect();
+        let mut string = string.to_string();
+        self.apply_in_place(&mut string);
+        string
+    }
+
+    /// Apply the edit in place, shifting only the bytes after the edited char. Returns the deleted
+    /// or substituted char.
+    pub(crate) fn apply_in_place(&self, string: &mut String) -> Option<char> {
+        if string.is_ascii() {
+            assert!(self.index() <= string.len(), "edit is in bounds");
+            return self.apply_at(string, self.index());
+        }
+
+        let offset = string
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain([string.len()])
+            .nth(self.index())
+            .expect("edit is in bounds");
+        self.apply_at(string, offset)
+    }
+
+    /// The edit turning the result of applying this one to `string` back into `string`.
+    pub(crate) fn undo(&self, string: &str) -> Edit {
+        let old = || {
+            string
+                .chars()
+                .nth(self.index())
+                .expect("edited char exists")
+        };
+        match *self {
+            Edit::Insert { index, .. } => Edit::Delete { index },
+            Edit::Delete { index } => Edit::Insert { c: old(), index },
+            Edit::Substitute { index, .. } => Edit::Substitute { c: old(), index },
+        }
+    }
 
+    /// The char index this edit applies to.
+    pub fn index(&self) -> usize {
         match self {
-            Edit::Insert { c, index } => {
-                chars.insert(*index, *c);
-            }
-            Edit::Delete { index } => {
-                chars.remove(*index);
-            }
-            Edit::Substitute { c, index } => {
-                chars[*index] = *c;
+            Edit::Insert { index, .. }
+            | Edit::Delete { index }
+            | Edit::Substitute { index, .. } => *index,
+        }
+    }
+
+    /// The UTF-8 byte offset in `string` this edit applies to, i.e. the byte offset of the char at
+    /// [`Edit::index`] or `string.len()` when inserting at the end.
+    pub fn byte_offset(&self, string: &str) -> usize {
+        string
+            .char_indices()
+            .nth(self.index())
+            .map_or(string.len(), |(offset, _)| offset)
+    }
+
+    /// Apply the edit in place given its already known `byte_offset` into `string`. Returns the
+    /// deleted or substituted char.
+    pub(crate) fn apply_at(
+        &self,
+        string: &mut impl EditBuffer,
+        byte_offset: usize,
+    ) -> Option<char> {
+        match self {
+            Edit::Insert { c, .. } => {
+                string.insert(byte_offset, *c);
+                None
             }
+            Edit::Delete { .. } => Some(string.remove(byte_offset)),
+            Edit::Substitute { c, .. } => Some(string.substitute(byte_offset, *c)),
+        }
+    }
+}
+
+/// A string edits apply to in place, at byte offsets of its chars.
+pub(crate) trait EditBuffer: Deref<Target = str> {
+    fn insert(&mut self, byte_offset: usize, c: char);
+
+    fn remove(&mut self, byte_offset: usize) -> char;
+
+    /// Replace the char at `byte_offset` with `c`, returning the replaced one.
+    fn substitute(&mut self, byte_offset: usize, c: char) -> char;
+}
+
+impl EditBuffer for String {
+    fn insert(&mut self, byte_offset: usize, c: char) {
+        String::insert(self, byte_offset, c);
+    }
+
+    fn remove(&mut self, byte_offset: usize) -> char {
+        String::remove(self, byte_offset)
+    }
+
+    fn substitute(&mut self, byte_offset: usize, c: char) -> char {
+        let old = self[byte_offset..]
+            .chars()
+            .next()
+            .expect("substituted char exists");
+        let mut buffer = [0; 4];
+        self.replace_range(
+            byte_offset..byte_offset + old.len_utf8(),
+            c.encode_utf8(&mut buffer),
+        );
+        old
+    }
+}
+
+impl<A: Array<Item = u8>> EditBuffer for SmallString<A> {
+    fn insert(&mut self, byte_offset: usize, c: char) {
+        SmallString::insert(self, byte_offset, c);
+    }
+
+    fn remove(&mut self, byte_offset: usize) -> char {
+        SmallString::remove(self, byte_offset)
+    }
+
+    fn substitute(&mut self, byte_offset: usize, c: char) -> char {
+        let old = SmallString::remove(self, byte_offset);
+        SmallString::insert(self, byte_offset, c);
+        old
+    }
+}
+
+/// An [`Edit`] packed into 8 bytes, half the size of the enum, to keep long scripts compact.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PackedEdit {
+    index: u32,
+    // The char in the low bits and the kind of edit above them.
+    op: u32,
+}
+
+const CHAR_BITS: u32 = 21;
+const CHAR_MASK: u32 = (1 << CHAR_BITS) - 1;
+const INSERT: u32 = 0;
+const DELETE: u32 = 1;
+const SUBSTITUTE: u32 = 2;
+
+impl PackedEdit {
+    pub fn unpack(self) -> Edit {
+        let index = self.index as usize;
+        let c = || char::from_u32(self.op & CHAR_MASK).expect("packed a char");
+        match self.op >> CHAR_BITS {
+            INSERT => Edit::Insert { c: c(), index },
+            DELETE => Edit::Delete { index },
+            _ => Edit::Substitute { c: c(), index },
         }
+    }
+
+    /// See [`Edit::index`].
+    pub fn index(self) -> usize {
+        self.index as usize
+    }
+
+    /// The inserted or substituted char, or None for deletions.
+    pub fn c(self) -> Option<char> {
+        match self.unpack() {
+            Edit::Insert { c, .. } | Edit::Substitute { c, .. } => Some(c),
+            Edit::Delete { .. } => None,
+        }
+    }
+}
+
+/// Pack `edit`, whose index the caller knows to fit in a `u32`.
+pub(crate) fn pack(edit: Edit) -> PackedEdit {
+    PackedEdit::try_from(edit).expect("edit index fits in a u32")
+}
+
+/// Fails if the index doesn't fit in a `u32`.
+impl TryFrom<Edit> for PackedEdit {
+    type Error = TryFromIntError;
+
+    fn try_from(edit: Edit) -> Result<Self, Self::Error> {
+        let index = u32::try_from(edit.index())?;
+        let op = match edit {
+            Edit::Insert { c, .. } => INSERT << CHAR_BITS | c as u32,
+            Edit::Delete { .. } => DELETE << CHAR_BITS,
+            Edit::Substitute { c, .. } => SUBSTITUTE << CHAR_BITS | c as u32,
+        };
+        Ok(Self { index, op })
+    }
+}
 
-        String::from_iter(chars.iter())
+impl From<PackedEdit> for Edit {
+    fn from(packed: PackedEdit) -> Self {
+        packed.unpack()
+    }
+}
+
+impl fmt::Debug for PackedEdit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.unpack().fmt(f)
     }
 }
 
@@ -47,4 +229,60 @@ mod tests {
     fn substitute_out_of_bounds_panics() {
         Substitute { c: 'a', index: 1 }.apply("");
     }
+
+    #[test]
+    #[should_panic]
+    fn delete_past_the_end_panics() {
+        Delete { index: 1 }.apply("a");
+    }
+
+    #[test]
+    fn packing_round_trips() {
+        assert_eq!(size_of::<PackedEdit>(), 8);
+        for edit in [
+            Insert {
+                c: char::MAX,
+                index: 0,
+            },
+            Delete {
+                index: u32::MAX as usize,
+            },
+            Substitute { c: '\0', index: 7 },
+        ] {
+            let packed = PackedEdit::try_from(edit).unwrap();
+            assert_eq!(packed.unpack(), edit);
+            assert_eq!(packed.index(), edit.index());
+        }
+        assert_eq!(PackedEdit::try_from(Delete { index: 3 }).unwrap().c(), None);
+        assert!(
+            PackedEdit::try_from(Delete {
+                index: u32::MAX as usize + 1
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn byte_offset_accounts_for_multibyte_chars() {
+        assert_eq!(Delete { index: 0 }.byte_offset("čau"), 0);
+        assert_eq!(Delete { index: 1 }.byte_offset("čau"), 2);
+        assert_eq!(Insert { c: 'x', index: 3 }.byte_offset("čau"), 4);
+    }
+
+    #[test]
+    fn apply_at_matches_apply() {
+        let string = "ž🦀a";
+        for edit in [
+            Insert { c: 'é', index: 1 },
+            Delete { index: 1 },
+            Substitute { c: 'b', index: 1 },
+            Substitute {
+                c: '🦀', index: 2
+            },
+        ] {
+            let mut in_place = string.to_string();
+            edit.apply_at(&mut in_place, edit.byte_offset(string));
+            assert_eq!(in_place, edit.apply(string), "{edit:?}");
+        }
+    }
 }
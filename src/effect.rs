@@ -0,0 +1,271 @@
+use std::collections::VecDeque;
+
+use crate::{Typing, confusables, edit::Edit, rng::Rng};
+
+/// A visual effect layered over the planned edits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Effect {
+    /// Pass every inserted or substituted char through `steps` random glyphs drawn from `alphabet`
+    /// before it settles on its final value.
+    Scramble { steps: usize, alphabet: Alphabet },
+    /// Route every substitution through a chain of up to `max_steps` intermediate lookalike
+    /// glyphs, e.g. `o` through `σ` into `s`, for a gradual shapeshifting look. Substitutions
+    /// without such a chain are left alone.
+    Shapeshift { max_steps: usize },
+    /// Pass every inserted or substituted char that has a leetspeak equivalent in the map through
+    /// it first, e.g. `3` before `e`.
+    Leetspeak(LeetMap),
+    /// Type the inserted and substituted chars like a person would, mistyping some of them and
+    /// correcting them with a backspace.
+    Typing(Typing),
+}
+
+/// The set of glyphs effects draw random intermediate chars from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    chars: Vec<char>,
+}
+
+impl Alphabet {
+    /// Create an alphabet of the distinct chars of `chars`. Falls back to the default alphabet if
+    /// `chars` is empty.
+    pub fn new(chars: &str) -> Self {
+        let mut unique: Vec<char> = Vec::new();
+        for c in chars.chars() {
+            if !unique.contains(&c) {
+                unique.push(c);
+            }
+        }
+
+        if unique.is_empty() {
+            return Self::default();
+        }
+
+        Self { chars: unique }
+    }
+
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    fn pick(&self, rng: &mut Rng) -> char {
+        self.chars[rng.below(self.chars.len())]
+    }
+}
+
+impl Default for Alphabet {
+    /// Printable ASCII without the space.
+    fn default() -> Self {
+        Self {
+            chars: ('!'..='~').collect(),
+        }
+    }
+}
+
+/// The leetspeak equivalents of chars used by [`Effect::Leetspeak`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeetMap {
+    // (char, equivalent), looked up by the char.
+    pairs: Vec<(char, char)>,
+}
+
+impl LeetMap {
+    /// A map of the given `(char, equivalent)` pairs. The first pair wins for repeated chars.
+    pub fn new(pairs: impl IntoIterator<Item = (char, char)>) -> Self {
+        Self {
+            pairs: pairs.into_iter().collect(),
+        }
+    }
+
+    pub fn get(&self, c: char) -> Option<char> {
+        self.pairs
+            .iter()
+            .find(|(from, _)| *from == c)
+            .map(|(_, to)| *to)
+    }
+}
+
+impl Default for LeetMap {
+    /// The common digit equivalents of lower and uppercase letters.
+    fn default() -> Self {
+        let pairs = [
+            ('a', '4'),
+            ('b', '8'),
+            ('e', '3'),
+            ('g', '9'),
+            ('i', '1'),
+            ('l', '1'),
+            ('o', '0'),
+            ('s', '5'),
+            ('t', '7'),
+            ('z', '2'),
+        ];
+        Self::new(
+            pairs
+                .into_iter()
+                .flat_map(|(c, leet)| [(c, leet), (c.to_ascii_uppercase(), leet)]),
+        )
+    }
+}
+
+impl Effect {
+    /// Expand the `edits` planned for `start` into the edits that are actually animated.
+    pub(crate) fn apply(
+        &self,
+        start: &str,
+        edits: VecDeque<Edit>,
+        rng: &mut Rng,
+    ) -> VecDeque<Edit> {
+        match self {
+            Effect::Scramble { steps, alphabet } => edits
+                .into_iter()
+                .flat_map(|edit| {
+                    let (c, index) = match edit {
+                        Edit::Insert { c, index } | Edit::Substitute { c, index } if *steps > 0 => {
+                            (c, index)
+                        }
+                        _ => return vec![edit],
+                    };
+
+                    let mut expanded = Vec::with_capacity(steps + 1);
+                    // The first glyph takes the place of the original edit, the rest substitute it.
+                    expanded.push(match edit {
+                        Edit::Insert { .. } => Edit::Insert {
+                            c: alphabet.pick(rng),
+                            index,
+                        },
+                        _ => Edit::Substitute {
+                            c: alphabet.pick(rng),
+                            index,
+                        },
+                    });
+                    expanded.extend((1..*steps).map(|_| Edit::Substitute {
+                        c: alphabet.pick(rng),
+                        index,
+                    }));
+                    expanded.push(Edit::Substitute { c, index });
+                    expanded
+                })
+                .collect(),
+            Effect::Shapeshift { max_steps } => {
+                let mut chars: Vec<char> = start.chars().collect();
+                let mut expanded = VecDeque::with_capacity(edits.len());
+                for edit in edits {
+                    match edit {
+                        Edit::Insert { c, index } => chars.insert(index, c),
+                        Edit::Delete { index } => {
+                            chars.remove(index);
+                        }
+                        Edit::Substitute { c, index } => {
+                            let chain = confusables::chain(chars[index], c, *max_steps);
+                            expanded.extend(
+                                (chain.into_iter().flatten())
+                                    .map(|c| Edit::Substitute { c, index }),
+                            );
+                            chars[index] = c;
+                        }
+                    }
+                    expanded.push_back(edit);
+                }
+                expanded
+            }
+            Effect::Leetspeak(map) => edits
+                .into_iter()
+                .flat_map(|edit| match edit {
+                    Edit::Insert { c, index } if let Some(leet) = map.get(c) => vec![
+                        Edit::Insert { c: leet, index },
+                        Edit::Substitute { c, index },
+                    ],
+                    Edit::Substitute { c, index } if let Some(leet) = map.get(c) => vec![
+                        Edit::Substitute { c: leet, index },
+                        Edit::Substitute { c, index },
+                    ],
+                    edit => vec![edit],
+                })
+                .collect(),
+            Effect::Typing(typing) => typing.apply(edits, rng),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levenshtein::compute_edit_sequence;
+
+    fn apply_all(start: &str, edits: &VecDeque<Edit>) -> Vec<String> {
+        let mut frames = vec![start.to_string()];
+        for edit in edits {
+            frames.push(edit.apply(frames.last().expect("non-empty")));
+        }
+        frames
+    }
+
+    #[test]
+    fn scramble_draws_from_alphabet_and_settles_on_target() {
+        let effect = Effect::Scramble {
+            steps: 3,
+            alphabet: Alphabet::new("01"),
+        };
+        let edits = compute_edit_sequence("abc", "xbcd");
+
+        let scrambled = effect.apply("abc", edits.clone(), &mut Rng::new(1));
+
+        // Each of the substitution and insertion gets 3 extra steps.
+        assert_eq!(scrambled.len(), edits.len() + 2 * 3);
+
+        let frames = apply_all("abc", &scrambled);
+        assert_eq!(frames.last().expect("non-empty"), "xbcd");
+        assert!(
+            frames
+                .iter()
+                .all(|frame| frame.chars().all(|c| "abcdx01".contains(c)))
+        );
+    }
+
+    #[test]
+    fn zero_steps_changes_nothing() {
+        let effect = Effect::Scramble {
+            steps: 0,
+            alphabet: Alphabet::default(),
+        };
+        let edits = compute_edit_sequence("kitten", "mittens");
+
+        assert_eq!(
+            effect.apply("kitten", edits.clone(), &mut Rng::new(1)),
+            edits
+        );
+    }
+
+    #[test]
+    fn shapeshift_goes_through_lookalikes() {
+        let effect = Effect::Shapeshift { max_steps: 2 };
+        let edits = compute_edit_sequence("ox", "sxy");
+
+        let shapeshifted = effect.apply("ox", edits, &mut Rng::new(1));
+
+        assert_eq!(
+            apply_all("ox", &shapeshifted),
+            vec!["ox", "σx", "sx", "sxy"]
+        );
+    }
+
+    #[test]
+    fn leetspeak_passes_through_equivalents() {
+        let effect = Effect::Leetspeak(LeetMap::default());
+        let edits = compute_edit_sequence("hat", "heat");
+
+        let leet = effect.apply("hat", edits, &mut Rng::new(1));
+
+        assert_eq!(apply_all("hat", &leet), vec!["hat", "h3at", "heat"]);
+        assert_eq!(LeetMap::new([('x', '%')]).get('x'), Some('%'));
+        assert_eq!(LeetMap::default().get('E'), Some('3'));
+    }
+
+    #[test]
+    fn alphabet_deduplicates_and_defaults() {
+        assert_eq!(Alphabet::new("ababc").chars(), &['a', 'b', 'c']);
+        assert_eq!(Alphabet::new(""), Alphabet::default());
+        assert_eq!(Alphabet::default().chars().len(), 94);
+    }
+}
@@ -0,0 +1,25 @@
+use crate::edit::Edit;
+
+/// Whether two chars are alike enough to keep one instead of substituting the other while
+/// morphing.
+pub(crate) type CharEquivalence = dyn Fn(char, char) -> bool + Send + Sync;
+
+/// The substitutions of the chars kept by `edits` for merely equivalent ones of `target`, which
+/// turn the value `edits` leave `start` at into the exact target.
+pub(crate) fn settle(
+    start: &str,
+    edits: impl IntoIterator<Item = Edit>,
+    target: &str,
+) -> Vec<Edit> {
+    let mut value = start.to_string();
+    for edit in edits {
+        edit.apply_in_place(&mut value);
+    }
+    value
+        .chars()
+        .zip(target.chars())
+        .enumerate()
+        .filter(|(_, (kept, c))| kept != c)
+        .map(|(index, (_, c))| Edit::Substitute { c, index })
+        .collect()
+}
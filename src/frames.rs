@@ -0,0 +1,302 @@
+use std::collections::VecDeque;
+
+use crate::{edit::Edit, levenshtein::compute_edit_sequence, motion::reduced_motion};
+
+/// Every frame of a morph from start to target, both included. See [`morph_frames`].
+///
+/// Frames can be taken from both ends, e.g. to play the same morph backwards.
+#[derive(Debug, Clone)]
+pub struct Frames {
+    edits: Vec<Edit>,
+    // The edit undoing every edit, i.e. turning the string after it back into the one before it.
+    undos: Vec<Edit>,
+    // The string after the first `front_edits` edits.
+    front: String,
+    front_edits: usize,
+    // The string after the first `back_edits` edits.
+    back: String,
+    back_edits: usize,
+    // The number of edits applied by the next frame yielded from the front, and by the next one
+    // yielded from the back. None once all frames were yielded.
+    next_frames: Option<(usize, usize)>,
+    // Number of edits between yielded frames.
+    stride: usize,
+}
+
+/// Iterate over every frame of a morph from `start` to `target`, including both. For one-shot
+/// animations that don't need the state of a [`MorphingString`](crate::MorphingString).
+pub fn morph_frames(start: &str, target: &str) -> Frames {
+    Frames::new(start.to_string(), compute_edit_sequence(start, target))
+}
+
+impl Frames {
+    pub(crate) fn new(start: String, edits: VecDeque<Edit>) -> Self {
+        let mut undos = Vec::with_capacity(edits.len());
+        let mut back = start.clone();
+        for edit in &edits {
+            undos.push(edit.undo(&back));
+            back = edit.apply(&back);
+        }
+
+        Self {
+            front: start,
+            front_edits: 0,
+            back,
+            back_edits: edits.len(),
+            next_frames: Some((0, edits.len())),
+            edits: edits.into(),
+            undos,
+            stride: 1,
+        }
+    }
+
+    /// Yield only every `n`-th frame after the start, always including the target, e.g. to play
+    /// long morphs in a bounded number of frames. The edits stay the same.
+    pub fn every(mut self, n: usize) -> Self {
+        self.stride = n.max(1);
+        self
+    }
+
+    fn stride(&self) -> usize {
+        if reduced_motion() {
+            self.edits.len().max(1)
+        } else {
+            self.stride
+        }
+    }
+
+    /// The index among all yielded frames of the one after `edits` edits.
+    fn frame_index(&self, edits: usize) -> usize {
+        edits.div_ceil(self.stride())
+    }
+}
+
+impl Iterator for Frames {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (front, back) = self.next_frames?;
+        for edit in &self.edits[self.front_edits..front] {
+            edit.apply_in_place(&mut self.front);
+        }
+        self.front_edits = front;
+
+        self.next_frames = (front < back)
+            .then(|| {
+                let stride = self.stride();
+                ((front / stride + 1) * stride).min(back)
+            })
+            .map(|front| (front, back));
+        Some(self.front.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.next_frames.map_or(0, |(front, back)| {
+            self.frame_index(back) - self.frame_index(front) + 1
+        });
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Frames {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (front, back) = self.next_frames?;
+        for undo in self.undos[back..self.back_edits].iter().rev() {
+            undo.apply_in_place(&mut self.back);
+        }
+        self.back_edits = back;
+
+        self.next_frames = (front < back)
+            .then(|| {
+                let stride = self.stride();
+                ((back - 1) / stride * stride).max(front)
+            })
+            .map(|back| (front, back));
+        Some(self.back.clone())
+    }
+}
+
+impl ExactSizeIterator for Frames {}
+
+/// Adapts an iterator of strings into the frames of a morph going through all of them. See
+/// [`MorphingExt::morphing`].
+#[derive(Debug, Clone)]
+pub struct MorphingFrames<I> {
+    items: I,
+    frames: Option<Frames>,
+    last: Option<String>,
+    dwell: usize,
+    remaining_dwell: usize,
+    stride: usize,
+}
+
+/// Extension trait adapting any iterator of strings with [`morphing`](MorphingExt::morphing).
+pub trait MorphingExt: Iterator + Sized
+where
+    Self::Item: AsRef<str>,
+{
+    /// Morph through the items in order. The first item is yielded as is, then the frames of the
+    /// morph into every next item follow, each ending with the item itself.
+    fn morphing(self) -> MorphingFrames<Self> {
+        MorphingFrames {
+            items: self,
+            frames: None,
+            last: None,
+            dwell: 0,
+            remaining_dwell: 0,
+            stride: 1,
+        }
+    }
+}
+
+impl<I> MorphingExt for I
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+}
+
+impl<I> MorphingFrames<I> {
+    /// Repeat every item `frames` more times once it's reached, before morphing into the next one.
+    pub fn with_dwell(mut self, frames: usize) -> Self {
+        self.dwell = frames;
+        self
+    }
+
+    /// Yield only every `n`-th frame of every morph, always including the items. See
+    /// [`Frames::every`].
+    pub fn every(mut self, n: usize) -> Self {
+        self.stride = n;
+        self
+    }
+}
+
+impl<I> Iterator for MorphingFrames<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.remaining_dwell > 0 {
+                self.remaining_dwell -= 1;
+                return self.last.clone();
+            }
+
+            if let Some(frame) = self.frames.as_mut().and_then(Iterator::next) {
+                if self.frames.as_ref().is_some_and(|frames| frames.len() == 0) {
+                    self.remaining_dwell = self.dwell;
+                }
+                self.last = Some(frame.clone());
+                return Some(frame);
+            }
+
+            let item = self.items.next()?;
+            let item = item.as_ref();
+            let frames = match &self.last {
+                Some(last) if last != item => {
+                    let mut frames = morph_frames(last, item).every(self.stride);
+                    // The start was yielded as the last frame of the previous item already.
+                    frames.next();
+                    frames
+                }
+                _ => morph_frames(item, item),
+            };
+            self.frames = Some(frames);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_include_both_endpoints() {
+        let frames = morph_frames("kitten", "mittens");
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(
+            frames.collect::<Vec<_>>(),
+            vec!["kitten", "mitten", "mittens"]
+        );
+    }
+
+    #[test]
+    fn every_keeps_the_target() {
+        let frames = morph_frames("", "abcde").every(2);
+
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames.collect::<Vec<_>>(), vec!["", "ab", "abcd", "abcde"]);
+    }
+
+    #[test]
+    fn equal_strings_yield_a_single_frame() {
+        assert_eq!(morph_frames("abc", "abc").collect::<Vec<_>>(), vec!["abc"]);
+    }
+
+    #[test]
+    fn morphing_goes_through_every_item() {
+        let frames: Vec<_> = ["kitten", "mittens", "mittens", "mitten"]
+            .into_iter()
+            .morphing()
+            .collect();
+
+        assert_eq!(
+            frames,
+            vec!["kitten", "mitten", "mittens", "mittens", "mitten"]
+        );
+    }
+
+    #[test]
+    fn dwell_repeats_every_item() {
+        let frames: Vec<_> = ["ab", "b"].into_iter().morphing().with_dwell(2).collect();
+
+        assert_eq!(frames, vec!["ab", "ab", "ab", "b", "b", "b"]);
+    }
+
+    #[test]
+    fn morphing_every_keeps_the_items() {
+        let frames: Vec<_> = ["", "abc", ""].into_iter().morphing().every(2).collect();
+
+        assert_eq!(frames, vec!["", "ab", "abc", "c", ""]);
+    }
+
+    #[test]
+    fn frames_play_backwards() {
+        let frames = morph_frames("kitten", "sitting");
+        let mut backwards: Vec<_> = frames.clone().rev().collect();
+        backwards.reverse();
+
+        assert_eq!(backwards, frames.collect::<Vec<_>>());
+        assert_eq!(
+            morph_frames("", "abcde").every(2).rev().collect::<Vec<_>>(),
+            vec!["abcde", "abcd", "ab", ""]
+        );
+    }
+
+    #[test]
+    fn frames_meet_in_the_middle() {
+        let mut frames = morph_frames("", "abc");
+
+        assert_eq!(frames.next_back().unwrap(), "abc");
+        assert_eq!(frames.next().unwrap(), "");
+        assert_eq!(frames.next_back().unwrap(), "ab");
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames.next().unwrap(), "a");
+        assert_eq!(frames.next(), None);
+        assert_eq!(frames.next_back(), None);
+    }
+
+    #[test]
+    fn len_shrinks_as_frames_are_yielded() {
+        let mut frames = morph_frames("", "abc");
+
+        assert_eq!(frames.len(), 4);
+        frames.next();
+        frames.next();
+        assert_eq!(frames.len(), 2);
+    }
+}
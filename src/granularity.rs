@@ -0,0 +1,85 @@
+/// The units a morph advances by. See [`MorphingString::with_granularity`].
+///
+/// [`MorphingString::with_granularity`]: crate::MorphingString::with_granularity
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Granularity {
+    /// Every advance applies a single edit.
+    #[default]
+    Char,
+    /// Every advance completes a word. See [`MorphingString::with_word_grouping`].
+    ///
+    /// [`MorphingString::with_word_grouping`]: crate::MorphingString::with_word_grouping
+    Word,
+    /// Every advance completes a line.
+    Line,
+    /// Pick one of the above for every morph based on the length and similarity of the strings.
+    Auto(AutoGranularity),
+}
+
+/// The thresholds [`Granularity::Auto`] picks a granularity by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoGranularity {
+    /// Morph by words when the longer of the strings has at least this many chars.
+    pub word_from: usize,
+    /// Morph by lines when the longer of the strings has at least this many chars.
+    pub line_from: usize,
+    /// Use the next finer granularity when the number of edits relative to the length of the
+    /// longer string is below this fraction. Small changes of long strings are best shown in
+    /// detail.
+    pub similar_below: f64,
+}
+
+impl Default for AutoGranularity {
+    fn default() -> Self {
+        Self {
+            word_from: 200,
+            line_from: 2000,
+            similar_below: 0.1,
+        }
+    }
+}
+
+impl Granularity {
+    /// Resolve [`Granularity::Auto`] for a morph from `start` to `target` taking `edits` edits.
+    pub(crate) fn resolve(self, start: &str, target: &str, edits: usize) -> Granularity {
+        let Granularity::Auto(thresholds) = self else {
+            return self;
+        };
+
+        let len = start.chars().count().max(target.chars().count());
+        let by_length = if len >= thresholds.line_from {
+            Granularity::Line
+        } else if len >= thresholds.word_from {
+            Granularity::Word
+        } else {
+            Granularity::Char
+        };
+
+        if (edits as f64) < thresholds.similar_below * len as f64 {
+            match by_length {
+                Granularity::Line => Granularity::Word,
+                _ => Granularity::Char,
+            }
+        } else {
+            by_length
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_picks_by_length_and_similarity() {
+        let auto = Granularity::Auto(AutoGranularity::default());
+        let paragraph = "word ".repeat(50);
+        let document = "line\n".repeat(500);
+
+        assert_eq!(auto.resolve("short", "shirt", 1), Granularity::Char);
+        assert_eq!(auto.resolve(&paragraph, "", 250), Granularity::Word);
+        assert_eq!(auto.resolve(&document, "", 2500), Granularity::Line);
+        // Few edits of a long document.
+        assert_eq!(auto.resolve(&document, &document, 3), Granularity::Word);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::edit::Edit;
+
+/// How a char of a frame changed, or is about to change. See [`MorphingString::highlights`].
+///
+/// [`MorphingString::highlights`]: crate::MorphingString::highlights
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    /// Inserted by the last step.
+    Inserted,
+    /// Substituted by the last step.
+    Substituted,
+    /// Deleted by the next step.
+    Deleting,
+}
+
+/// Highlight every char of a value `len` chars long that `last_step` produced and that
+/// `next_step` is about to be applied to.
+pub(crate) fn highlights(
+    len: usize,
+    last_step: &[Edit],
+    next_step: impl IntoIterator<Item = Edit>,
+) -> Vec<Option<Highlight>> {
+    let inserted = last_step
+        .iter()
+        .filter(|edit| matches!(edit, Edit::Insert { .. }))
+        .count();
+    let deleted = last_step
+        .iter()
+        .filter(|edit| matches!(edit, Edit::Delete { .. }))
+        .count();
+
+    // Replay the last step on marks of the value before it.
+    let mut marks = vec![None; len + deleted - inserted];
+    for edit in last_step {
+        match *edit {
+            Edit::Insert { index, .. } => marks.insert(index, Some(Highlight::Inserted)),
+            Edit::Delete { index } => {
+                marks.remove(index);
+            }
+            Edit::Substitute { index, .. } => {
+                marks[index].get_or_insert(Highlight::Substituted);
+            }
+        }
+    }
+
+    // Replay the next step keeping track of which char of the value every char comes from.
+    let mut origins: Vec<Option<usize>> = (0..len).map(Some).collect();
+    for edit in next_step {
+        match edit {
+            Edit::Insert { index, .. } => origins.insert(index, None),
+            Edit::Delete { index } => {
+                if let Some(origin) = origins.remove(index) {
+                    marks[origin] = Some(Highlight::Deleting);
+                }
+            }
+            Edit::Substitute { .. } => {}
+        }
+    }
+
+    marks
+}
+
+/// Whether `edits`, applied to a value `len` chars long, substitute or delete each of its chars.
+pub(crate) fn touched(len: usize, edits: impl IntoIterator<Item = Edit>) -> Vec<bool> {
+    let mut touched = vec![false; len];
+    let mut origins: Vec<Option<usize>> = (0..len).map(Some).collect();
+    for edit in edits {
+        match edit {
+            Edit::Insert { index, .. } => origins.insert(index, None),
+            Edit::Delete { index } => {
+                if let Some(origin) = origins.remove(index) {
+                    touched[origin] = true;
+                }
+            }
+            Edit::Substitute { index, .. } => {
+                if let Some(origin) = origins[index].take() {
+                    touched[origin] = true;
+                }
+            }
+        }
+    }
+    touched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Edit::*;
+    use Highlight::*;
+
+    #[test]
+    fn marks_follow_evolving_value() {
+        // "abc" -> "xbyc" -> "byc"
+        let last_step = [Insert { c: 'x', index: 0 }, Substitute { c: 'y', index: 2 }];
+        let next_step = [Insert { c: 'z', index: 0 }, Delete { index: 1 }];
+
+        assert_eq!(
+            highlights(4, &last_step, next_step),
+            vec![Some(Deleting), None, Some(Substituted), None]
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use std::{collections::HashMap, ops::Range};
+
+use crate::{
+    edit::Edit, levenshtein::compute_edit_sequence, planner::MorphPlanner, script::EditScript,
+};
+
+/// A planner diffing words or lines with the histogram algorithm used by git, which anchors on the
+/// tokens occurring the fewest times. Unlike Levenshtein over tokens, that keeps distinctive lines
+/// like function signatures aligned and so gives better structured scripts for prose and code.
+///
+/// Tokens left unmatched morph char by char. Pairs well with the word and line
+/// [`Granularity`](crate::Granularity) of a [`MorphingString`](crate::MorphingString).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistogramPlanner {
+    tokens: Tokens,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tokens {
+    // Runs of whitespace and of other chars.
+    Words,
+    // Lines including their line break.
+    Lines,
+}
+
+impl HistogramPlanner {
+    pub fn words() -> Self {
+        Self {
+            tokens: Tokens::Words,
+        }
+    }
+
+    pub fn lines() -> Self {
+        Self {
+            tokens: Tokens::Lines,
+        }
+    }
+
+    fn tokenize<'a>(&self, string: &'a str) -> Vec<&'a str> {
+        match self.tokens {
+            Tokens::Words => {
+                let mut tokens = Vec::new();
+                let mut start = 0;
+                let mut chars = string.char_indices().peekable();
+                while let Some((_, c)) = chars.next() {
+                    if let Some(&(next, next_c)) = chars.peek()
+                        && next_c.is_whitespace() != c.is_whitespace()
+                    {
+                        tokens.push(&string[start..next]);
+                        start = next;
+                    }
+                }
+                if start < string.len() {
+                    tokens.push(&string[start..]);
+                }
+                tokens
+            }
+            Tokens::Lines => string.split_inclusive('\n').collect(),
+        }
+    }
+}
+
+impl MorphPlanner for HistogramPlanner {
+    fn plan(&self, from: &str, to: &str) -> EditScript {
+        let from_tokens = self.tokenize(from);
+        let to_tokens = self.tokenize(to);
+        let mut matches = Vec::new();
+        histogram_diff(
+            &from_tokens,
+            &to_tokens,
+            0..from_tokens.len(),
+            0..to_tokens.len(),
+            &mut matches,
+        );
+        matches.push((from_tokens.len(), to_tokens.len()));
+
+        // Morph every gap between matched tokens char by char, left to right. The gaps before the
+        // current one already look like the target, so `offset` counts target chars.
+        let mut edits = Vec::new();
+        let mut offset = 0;
+        let (mut i, mut j) = (0, 0);
+        for (matched_i, matched_j) in matches {
+            let old = from_tokens[i..matched_i].concat();
+            let new = to_tokens[j..matched_j].concat();
+            edits.extend(
+                compute_edit_sequence(&old, &new)
+                    .into_iter()
+                    .map(|edit| match edit {
+                        Edit::Insert { c, index } => Edit::Insert {
+                            c,
+                            index: offset + index,
+                        },
+                        Edit::Delete { index } => Edit::Delete {
+                            index: offset + index,
+                        },
+                        Edit::Substitute { c, index } => Edit::Substitute {
+                            c,
+                            index: offset + index,
+                        },
+                    }),
+            );
+            offset += new.chars().count();
+            if let Some(token) = from_tokens.get(matched_i) {
+                offset += token.chars().count();
+            }
+            (i, j) = (matched_i + 1, matched_j + 1);
+        }
+        edits.into_iter().collect()
+    }
+}
+
+/// Collect the pairs of indexes of matching tokens of `a` and `b` within the given ranges, in
+/// ascending order.
+fn histogram_diff(
+    a: &[&str],
+    b: &[&str],
+    mut a_range: Range<usize>,
+    mut b_range: Range<usize>,
+    matches: &mut Vec<(usize, usize)>,
+) {
+    // Common prefix and suffix.
+    while !a_range.is_empty() && !b_range.is_empty() && a[a_range.start] == b[b_range.start] {
+        matches.push((a_range.start, b_range.start));
+        a_range.start += 1;
+        b_range.start += 1;
+    }
+    let mut suffix = Vec::new();
+    while !a_range.is_empty() && !b_range.is_empty() && a[a_range.end - 1] == b[b_range.end - 1] {
+        a_range.end -= 1;
+        b_range.end -= 1;
+        suffix.push((a_range.end, b_range.end));
+    }
+
+    if let Some((i, j, len)) = anchor(a, b, a_range.clone(), b_range.clone()) {
+        histogram_diff(a, b, a_range.start..i, b_range.start..j, matches);
+        matches.extend((0..len).map(|k| (i + k, j + k)));
+        histogram_diff(a, b, i + len..a_range.end, j + len..b_range.end, matches);
+    }
+    matches.extend(suffix.into_iter().rev());
+}
+
+/// The longest run of matching tokens starting with a token that occurs in `a` the fewest times,
+/// as (start in `a`, start in `b`, length).
+fn anchor(
+    a: &[&str],
+    b: &[&str],
+    a_range: Range<usize>,
+    b_range: Range<usize>,
+) -> Option<(usize, usize, usize)> {
+    let mut occurrences: HashMap<&str, Vec<usize>> = HashMap::new();
+    for i in a_range.clone() {
+        occurrences.entry(a[i]).or_default().push(i);
+    }
+
+    // (occurrences in `a`, start in `a`, start in `b`, length) of the best anchor so far.
+    let mut best: Option<(usize, usize, usize, usize)> = None;
+    for j in b_range.clone() {
+        let Some(positions) = occurrences.get(b[j]) else {
+            continue;
+        };
+        for &i in positions {
+            let len = (0..)
+                .take_while(|k| {
+                    a_range.contains(&(i + k)) && b_range.contains(&(j + k)) && a[i + k] == b[j + k]
+                })
+                .count();
+            let better = best.is_none_or(|(count, _, _, best_len)| {
+                positions.len() < count || (positions.len() == count && len > best_len)
+            });
+            if better {
+                best = Some((positions.len(), i, j, len));
+            }
+        }
+    }
+    best.map(|(_, i, j, len)| (i, j, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripts_produce_the_target() {
+        let pairs = [
+            ("the quick brown fox", "the slow brown dog jumps"),
+            ("a b a b", "b a b a"),
+            ("", "new text"),
+            ("old text", ""),
+            ("fn a() {\n}\n", "fn b() {\n}\n\nfn a() {\n}\n"),
+        ];
+
+        for (from, to) in pairs {
+            for planner in [HistogramPlanner::words(), HistogramPlanner::lines()] {
+                assert_eq!(planner.plan(from, to).apply(from), to, "{from:?} -> {to:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn unique_lines_anchor_the_diff() {
+        let from = "}\nfn a() {\n}\n";
+        let to = "}\nfn b() {\n}\nfn a() {\n}\n";
+
+        let script = HistogramPlanner::lines().plan(from, to);
+
+        // Only the new function gets typed, the existing one stays in place.
+        assert_eq!(script.len(), "fn b() {\n}\n".len());
+        assert!(
+            script
+                .iter()
+                .all(|edit| matches!(edit, Edit::Insert { .. }))
+        );
+    }
+}
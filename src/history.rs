@@ -0,0 +1,135 @@
+use std::collections::VecDeque;
+
+use crate::MorphingString;
+
+/// A bounded list of past targets with a cursor, like the history of a browser tab.
+#[derive(Debug, Clone)]
+pub(crate) struct History {
+    capacity: usize,
+    targets: VecDeque<String>,
+    position: usize,
+}
+
+impl History {
+    pub fn new(capacity: usize, current: String) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            targets: VecDeque::from([current]),
+            position: 0,
+        }
+    }
+
+    /// Record `target` after the current position, forgetting the targets forward of it and the
+    /// oldest ones over capacity.
+    pub fn push(&mut self, target: String) {
+        if self.targets[self.position] == target {
+            return;
+        }
+        self.targets.truncate(self.position + 1);
+        self.targets.push_back(target);
+        if self.targets.len() > self.capacity {
+            self.targets.pop_front();
+        }
+        self.position = self.targets.len() - 1;
+    }
+
+    /// Replace the target at the current position, forgetting the targets forward of it.
+    pub fn replace_current(&mut self, target: String) {
+        self.targets.truncate(self.position + 1);
+        self.targets[self.position] = target;
+    }
+
+    pub fn back(&mut self) -> Option<String> {
+        self.position = self.position.checked_sub(1)?;
+        Some(self.targets[self.position].clone())
+    }
+
+    pub fn forward(&mut self) -> Option<String> {
+        let target = self.targets.get(self.position + 1)?.clone();
+        self.position += 1;
+        Some(target)
+    }
+}
+
+impl MorphingString {
+    /// Remember up to `capacity` most recent targets so [`MorphingString::back`] and
+    /// [`MorphingString::forward`] can morph between them.
+    pub fn with_history(mut self, capacity: usize) -> Self {
+        self.history = Some(History::new(capacity, self.target.clone()));
+        self
+    }
+
+    /// Morph to the target set before the current one. Returns whether there was one, which is
+    /// never the case without [`MorphingString::with_history`].
+    pub fn back(&mut self) -> bool {
+        match self.history.as_mut().and_then(History::back) {
+            Some(target) => {
+                self.retarget(target);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Morph to the target that was current before the last [`MorphingString::back`]. Returns
+    /// whether there was one. Setting a new target forgets the targets forward of the current one.
+    pub fn forward(&mut self) -> bool {
+        match self.history.as_mut().and_then(History::forward) {
+            Some(target) => {
+                self.retarget(target);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MorphingString;
+
+    fn run(string: &mut MorphingString) -> &str {
+        while !string.advance().is_complete() {}
+        string.value()
+    }
+
+    #[test]
+    fn back_and_forward_walk_the_targets() {
+        let mut string = MorphingString::new("home".to_string()).with_history(10);
+        string.set_target("docs".to_string());
+        string.set_target("docs/api".to_string());
+
+        assert!(string.back());
+        assert_eq!(run(&mut string), "docs");
+        assert!(string.back());
+        assert_eq!(run(&mut string), "home");
+        assert!(!string.back());
+        assert!(string.forward());
+        assert_eq!(run(&mut string), "docs");
+
+        string.set_target("blog".to_string());
+        assert!(!string.forward());
+        assert!(string.back());
+        assert_eq!(run(&mut string), "docs");
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut string = MorphingString::new("a".to_string()).with_history(2);
+        string.set_target("b".to_string());
+        string.set_target("c".to_string());
+
+        assert!(string.back());
+        assert!(!string.back());
+        assert_eq!(run(&mut string), "b");
+    }
+
+    #[test]
+    fn without_history_there_is_nowhere_to_go() {
+        let mut string = MorphingString::new("a".to_string());
+        string.set_target("b".to_string());
+
+        assert!(!string.back());
+        assert!(!string.forward());
+    }
+}
@@ -1,42 +1,221 @@
-use std::collections::VecDeque;
+use std::{
+    cmp::Ordering,
+    collections::VecDeque,
+    ops::{Index, IndexMut},
+};
 
-use crate::edit::Edit;
+use crate::{
+    anchor::Anchor, confusables::confusable, edit::Edit, equivalence::CharEquivalence,
+    tie_break::TieBreak,
+};
+
+/// The cost of every kind of edit. The planned edits are the cheapest in total.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Costs {
+    pub insert: usize,
+    pub delete: usize,
+    pub substitute: usize,
+    /// The cost of substituting a char with one that looks alike, e.g. `O` with `0`, instead of
+    /// `substitute`. `None` treats lookalikes like any other chars.
+    pub lookalike_substitute: Option<usize>,
+}
+
+impl Default for Costs {
+    /// Every edit costs the same, minimizing the number of edits.
+    fn default() -> Self {
+        Self {
+            insert: 1,
+            delete: 1,
+            substitute: 1,
+            lookalike_substitute: None,
+        }
+    }
+}
+
+impl Costs {
+    /// Costs making substitutions with lookalike chars cheaper than any other edit, so morphs
+    /// take steps that barely change the appearance before bigger ones.
+    pub fn lookalike() -> Self {
+        Self {
+            insert: 2,
+            delete: 2,
+            substitute: 2,
+            lookalike_substitute: Some(1),
+        }
+    }
+
+    /// The cost of substituting `old` with the different char `new`.
+    fn substitution(&self, old: char, new: char) -> usize {
+        match self.lookalike_substitute {
+            Some(cost) if confusable(old, new) => cost,
+            _ => self.substitute,
+        }
+    }
+}
 
 /// Compute a sequence of [`Edit`]s that when applied onto `start` will turn it into `target`.
 /// The Edits have to be applied front to back.
 pub fn compute_edit_sequence(start: &str, target: &str) -> VecDeque<Edit> {
-    let start_chars: Vec<char> = start.chars().collect();
-    let target_chars: Vec<char> = target.chars().collect();
+    compute_weighted_edit_sequence(
+        start,
+        target,
+        &Costs::default(),
+        Anchor::default(),
+        TieBreak::default(),
+    )
+}
+
+/// A move through the dp matrix towards its top left corner.
+enum Move {
+    Diagonal,
+    Insert,
+    Delete,
+}
+
+/// Like [`compute_edit_sequence`] but minimizing the total cost of the edits rather than their
+/// number and choosing between equally cheap edits by `tie_break`, keeping the `anchor` end of the
+/// strings aligned by default.
+pub fn compute_weighted_edit_sequence(
+    start: &str,
+    target: &str,
+    costs: &Costs,
+    anchor: Anchor,
+    tie_break: TieBreak,
+) -> VecDeque<Edit> {
+    Scratch::default().edit_sequence(start, target, costs, anchor, tie_break, None)
+}
+
+/// Buffers for computing edit sequences, kept around to plan repeatedly without allocating them
+/// every time.
+#[derive(Debug, Default)]
+pub(crate) struct Scratch {
+    buffers: Buffers,
+    start_chars: Vec<char>,
+    target_chars: Vec<char>,
+}
+
+/// The buffers [`weighted_edit_sequence`] fills.
+#[derive(Debug, Default)]
+struct Buffers {
+    dp: Vec<usize>,
+    // The edits of the last plan, handed back once they've been consumed.
+    edits: VecDeque<Edit>,
+}
+
+impl Scratch {
+    /// [`compute_weighted_edit_sequence`] reusing the buffers. Chars `equivalent` considers
+    /// alike are kept as if they were equal.
+    pub fn edit_sequence(
+        &mut self,
+        start: &str,
+        target: &str,
+        costs: &Costs,
+        anchor: Anchor,
+        tie_break: TieBreak,
+        equivalent: Option<&CharEquivalence>,
+    ) -> VecDeque<Edit> {
+        // Most text is ASCII, where bytes are chars and don't need collecting.
+        if start.is_ascii() && target.is_ascii() {
+            return weighted_edit_sequence(
+                start.as_bytes(),
+                target.as_bytes(),
+                &mut self.buffers,
+                costs,
+                anchor,
+                tie_break,
+                equivalent,
+            );
+        }
+
+        self.start_chars.clear();
+        self.start_chars.extend(start.chars());
+        self.target_chars.clear();
+        self.target_chars.extend(target.chars());
+        weighted_edit_sequence(
+            &self.start_chars,
+            &self.target_chars,
+            &mut self.buffers,
+            costs,
+            anchor,
+            tie_break,
+            equivalent,
+        )
+    }
+
+    /// Hand back the edits of a plan once they've been consumed, for the next plan to reuse.
+    pub fn recycle(&mut self, mut edits: VecDeque<Edit>) {
+        edits.clear();
+        self.buffers.edits = edits;
+    }
+}
+
+/// A matrix stored row by row in a reusable buffer.
+struct Matrix<'a> {
+    cells: &'a mut [usize],
+    columns: usize,
+}
+
+impl Index<(usize, usize)> for Matrix<'_> {
+    type Output = usize;
+
+    fn index(&self, (row, column): (usize, usize)) -> &usize {
+        &self.cells[row * self.columns + column]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix<'_> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut usize {
+        &mut self.cells[row * self.columns + column]
+    }
+}
+
+/// [`compute_weighted_edit_sequence`] over either chars or the bytes of ASCII strings.
+fn weighted_edit_sequence<T: Copy + Eq + Into<char>>(
+    start_chars: &[T],
+    target_chars: &[T],
+    buffers: &mut Buffers,
+    costs: &Costs,
+    anchor: Anchor,
+    tie_break: TieBreak,
+    equivalent: Option<&CharEquivalence>,
+) -> VecDeque<Edit> {
     let start_len = start_chars.len();
     let target_len = target_chars.len();
+    let matches =
+        |a: T, b: T| a == b || equivalent.is_some_and(|equivalent| equivalent(a.into(), b.into()));
 
-    // Compute a matrix where dp[i][j] = minimal number of edits to convert a prefix of
+    // Compute a matrix where dp[(i, j)] = minimal cost of edits to convert a prefix of
     // start[0..i] to prefix of target[0..j].
-    let mut dp = vec![vec![0; target_len + 1]; start_len + 1];
+    buffers.dp.clear();
+    buffers.dp.resize((start_len + 1) * (target_len + 1), 0);
+    let mut dp = Matrix {
+        cells: &mut buffers.dp,
+        columns: target_len + 1,
+    };
 
-    #[expect(clippy::needless_range_loop)]
     for i in 1..=start_len {
         // Converting string of length i to an empty string takes i deletions.
-        dp[i][0] = i;
+        dp[(i, 0)] = i * costs.delete;
     }
     for j in 1..=target_len {
         // Converting an empty string into a string of length j takes j insertions.
-        dp[0][j] = j;
+        dp[(0, j)] = j * costs.insert;
     }
 
     for i in 1..=start_len {
         for j in 1..=target_len {
-            let substitution_distance = if start_chars[i - 1] == target_chars[j - 1] {
+            let substitution_distance = if matches(start_chars[i - 1], target_chars[j - 1]) {
                 // Chars actually match. Same distance as of the two shorter prefixes.
-                dp[i - 1][j - 1]
+                dp[(i - 1, j - 1)]
             } else {
                 // Chars differ so this is an actual substitutions for a *different* char.
-                dp[i - 1][j - 1] + 1
+                dp[(i - 1, j - 1)]
+                    + costs.substitution(start_chars[i - 1].into(), target_chars[j - 1].into())
             };
-            let deletion_distance = dp[i - 1][j] + 1;
-            let insertion_distance = dp[i][j - 1] + 1;
+            let deletion_distance = dp[(i - 1, j)] + costs.delete;
+            let insertion_distance = dp[(i, j - 1)] + costs.insert;
 
-            dp[i][j] = substitution_distance
+            dp[(i, j)] = substitution_distance
                 .min(deletion_distance)
                 .min(insertion_distance);
         }
@@ -45,47 +224,73 @@ pub fn compute_edit_sequence(start: &str, target: &str) -> VecDeque<Edit> {
     // Do a gradient-descent through the dp matrix backtracking the edits along the way.
     let mut i = start_len;
     let mut j = target_len;
-    let mut edits: VecDeque<Edit> = VecDeque::new();
+    let mut edits = std::mem::take(&mut buffers.edits);
+
+    // Among the optimal paths prefer the one pairing up chars along this diagonal (j - i) of the
+    // matrix, which keeps the anchored part of the strings aligned.
+    let preferred_diagonal = anchor.diagonal(start_len, target_len);
 
     while i > 0 || j > 0 {
         let edit = if i == 0 {
             // Getting to a non-empty string from an empty one can only be done with Insertions.
             Edit::Insert {
-                c: target_chars[j - 1],
+                c: target_chars[j - 1].into(),
                 index: 0,
             }
         } else if j == 0 {
             // Getting to an empty string from some non-empty one can only be done with Deletions.
             Edit::Delete { index: i - 1 }
-        } else if start_chars[i - 1] == target_chars[j - 1] {
-            // Chars are equal. Just move on in both strings.
-            i -= 1;
-            j -= 1;
-            continue;
         } else {
-            // chars are not equal and we have the choice of choosing any Edit. Choose the one that
-            // moves us to a position in the matrix that has the lowest Levenshtein distance.
-            [
-                (
-                    dp[i - 1][j - 1],
-                    Edit::Substitute {
-                        c: target_chars[j - 1],
-                        index: i - 1,
-                    },
-                ),
-                (
-                    dp[i][j - 1],
-                    Edit::Insert {
-                        c: target_chars[j - 1],
-                        index: i,
-                    },
-                ),
-                (dp[i - 1][j], Edit::Delete { index: i - 1 }),
-            ]
-            .iter()
-            .min_by_key(|(distance, _)| distance)
-            .expect("this is a non-empty list")
-            .1
+            // We may have the choice of several moves. Take one that moves us to a position in the
+            // matrix that keeps the total cost minimal, preferring moves towards the preferred
+            // diagonal.
+            let matching = matches(start_chars[i - 1], target_chars[j - 1]);
+            let diagonal = j as isize - i as isize;
+            let order = match tie_break {
+                TieBreak::Anchored => match (anchor, diagonal.cmp(&preferred_diagonal)) {
+                    // Backtracking from the ends greedily along the diagonal keeps the tail
+                    // aligned.
+                    (Anchor::Right, _) | (_, Ordering::Equal) => {
+                        [Move::Diagonal, Move::Insert, Move::Delete]
+                    }
+                    (_, Ordering::Greater) => [Move::Insert, Move::Diagonal, Move::Delete],
+                    (_, Ordering::Less) => [Move::Delete, Move::Diagonal, Move::Insert],
+                },
+                TieBreak::PreferSubstitutions => [Move::Diagonal, Move::Insert, Move::Delete],
+                TieBreak::PreferDeletions => [Move::Delete, Move::Diagonal, Move::Insert],
+                TieBreak::PreferInsertions => [Move::Insert, Move::Diagonal, Move::Delete],
+            };
+            let optimal = order
+                .into_iter()
+                .find(|step| match step {
+                    Move::Diagonal if matching => dp[(i - 1, j - 1)] == dp[(i, j)],
+                    Move::Diagonal => {
+                        let cost = costs
+                            .substitution(start_chars[i - 1].into(), target_chars[j - 1].into());
+                        dp[(i - 1, j - 1)] + cost == dp[(i, j)]
+                    }
+                    Move::Insert => dp[(i, j - 1)] + costs.insert == dp[(i, j)],
+                    Move::Delete => dp[(i - 1, j)] + costs.delete == dp[(i, j)],
+                })
+                .expect("some move leads to the minimal cost");
+
+            match optimal {
+                Move::Diagonal if matching => {
+                    // Chars are equal, or equivalent. Just move on in both strings.
+                    i -= 1;
+                    j -= 1;
+                    continue;
+                }
+                Move::Diagonal => Edit::Substitute {
+                    c: target_chars[j - 1].into(),
+                    index: i - 1,
+                },
+                Move::Insert => Edit::Insert {
+                    c: target_chars[j - 1].into(),
+                    index: i,
+                },
+                Move::Delete => Edit::Delete { index: i - 1 },
+            }
         };
 
         match edit {
@@ -126,9 +331,93 @@ pub fn compute_edit_sequence(start: &str, target: &str) -> VecDeque<Edit> {
     edits
 }
 
+/// Compute the Levenshtein distance between `start` and `target`, i.e. the number of edits
+/// [`compute_edit_sequence`] would produce, without backtracking the edits themselves.
+pub fn distance(start: &str, target: &str) -> usize {
+    if start.is_ascii() && target.is_ascii() {
+        return row_distance(start.bytes(), target.as_bytes());
+    }
+
+    let target_chars: Vec<char> = target.chars().collect();
+    row_distance(start.chars(), &target_chars)
+}
+
+/// [`distance`] over either chars or the bytes of ASCII strings.
+fn row_distance<T: Copy + Eq>(start: impl Iterator<Item = T>, target_chars: &[T]) -> usize {
+    // Only keep the previous and current row of the dp matrix around.
+    let mut previous: Vec<usize> = (0..=target_chars.len()).collect();
+    let mut current = vec![0; target_chars.len() + 1];
+
+    for (i, start_char) in start.enumerate() {
+        current[0] = i + 1;
+        for (j, target_char) in target_chars.iter().enumerate() {
+            let substitution_distance = previous[j] + usize::from(start_char != *target_char);
+            current[j + 1] = substitution_distance
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[target_chars.len()]
+}
+
+/// Like [`distance`] but gives up, returning `None`, as soon as the distance is known to exceed
+/// `max_distance`. Only a band of `2 * max_distance + 1` diagonals of the dp matrix is computed so
+/// this is much cheaper than [`distance`] for small thresholds.
+pub fn distance_within(start: &str, target: &str, max_distance: usize) -> Option<usize> {
+    let start_chars: Vec<char> = start.chars().collect();
+    let target_chars: Vec<char> = target.chars().collect();
+    let start_len = start_chars.len();
+    let target_len = target_chars.len();
+
+    if start_len.abs_diff(target_len) > max_distance {
+        return None;
+    }
+    // The distance never exceeds the longer length, so a larger band only risks overflows.
+    let max_distance = max_distance.min(start_len.max(target_len));
+
+    // Anything above max_distance is as good as infinite.
+    let infinity = max_distance + 1;
+    let mut previous: Vec<usize> = (0..=target_len).map(|j| j.min(infinity)).collect();
+    let mut current = vec![infinity; target_len + 1];
+
+    for i in 1..=start_len {
+        let low = i.saturating_sub(max_distance).max(1);
+        let high = (i + max_distance).min(target_len);
+
+        // Cells just outside the band are read when computing this and the next row.
+        current[low - 1] = if low == 1 { i.min(infinity) } else { infinity };
+        if high < target_len {
+            current[high + 1] = infinity;
+        }
+
+        for j in low..=high {
+            let substitution_distance =
+                previous[j - 1] + usize::from(start_chars[i - 1] != target_chars[j - 1]);
+            current[j] = substitution_distance
+                .min(previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(infinity);
+        }
+
+        if current[low - 1..=high]
+            .iter()
+            .all(|&distance| distance == infinity)
+        {
+            return None;
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    Some(previous[target_len]).filter(|&distance| distance <= max_distance)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stats::EditCounts;
     use Edit::*;
 
     struct Case {
@@ -286,6 +575,11 @@ mod tests {
         {
             let edits = compute_edit_sequence(start, target);
             assert_eq!(edits, *expected_edits, "{name}: edits match expectation");
+            assert_eq!(
+                distance(start, target),
+                edits.len(),
+                "{name}: distance matches number of edits"
+            );
 
             let mut string = start.to_string();
             for edit in edits {
@@ -298,4 +592,141 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn distance_within_matches_distance() {
+        let strings = [
+            "",
+            "a",
+            "kitten",
+            "sitting",
+            "mittens",
+            "sunday",
+            "saturday",
+            "žluťoučký kůň",
+            "zlutoucky kun",
+        ];
+
+        for start in strings {
+            for target in strings {
+                let exact = distance(start, target);
+                for max_distance in 0..15 {
+                    assert_eq!(
+                        distance_within(start, target, max_distance),
+                        (exact <= max_distance).then_some(exact),
+                        "{start:?} -> {target:?} within {max_distance}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn distance_within_unbounded() {
+        assert_eq!(distance_within("kitten", "sitting", usize::MAX), Some(3));
+        assert_eq!(distance_within("", "", usize::MAX), Some(0));
+    }
+
+    #[test]
+    fn lookalike_substitutions_are_preferred() {
+        let edits = compute_weighted_edit_sequence(
+            "O",
+            "a0",
+            &Costs::lookalike(),
+            Anchor::Left,
+            TieBreak::default(),
+        );
+
+        assert_eq!(
+            edits,
+            [Insert { c: 'a', index: 0 }, Substitute { c: '0', index: 1 }]
+        );
+    }
+
+    #[test]
+    fn tie_break_picks_the_edit_kind() {
+        let edits = |tie_break| {
+            let edits = compute_weighted_edit_sequence(
+                "ab",
+                "ba",
+                &Costs::default(),
+                Anchor::default(),
+                tie_break,
+            );
+            edits.iter().collect::<EditCounts>()
+        };
+
+        assert_eq!(edits(TieBreak::PreferSubstitutions).substitutions, 2);
+        assert_eq!(edits(TieBreak::PreferDeletions).deletes, 1);
+        assert_eq!(edits(TieBreak::PreferInsertions).inserts, 1);
+    }
+
+    #[test]
+    fn ascii_and_char_paths_agree() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("12:59", "13:00"),
+            ("O0o", "0oO"),
+            ("", "ab"),
+        ];
+
+        for (start, target) in pairs {
+            let start_chars: Vec<char> = start.chars().collect();
+            let target_chars: Vec<char> = target.chars().collect();
+            for anchor in [Anchor::Left, Anchor::Right] {
+                let costs = Costs::lookalike();
+                assert_eq!(
+                    weighted_edit_sequence(
+                        start.as_bytes(),
+                        target.as_bytes(),
+                        &mut Buffers::default(),
+                        &costs,
+                        anchor,
+                        TieBreak::default(),
+                        None
+                    ),
+                    weighted_edit_sequence(
+                        &start_chars,
+                        &target_chars,
+                        &mut Buffers::default(),
+                        &costs,
+                        anchor,
+                        TieBreak::default(),
+                        None
+                    ),
+                    "{start:?} -> {target:?}"
+                );
+            }
+            assert_eq!(
+                row_distance(start.bytes(), target.as_bytes()),
+                row_distance(start.chars(), &target_chars)
+            );
+        }
+    }
+
+    #[test]
+    fn reused_scratch_matches_fresh_computation() {
+        let mut scratch = Scratch::default();
+        let pairs = [
+            ("kitten", "sitting"),
+            ("a", "abcdefgh"),
+            ("čau", "ahoj"),
+            ("abcdefgh", "b"),
+        ];
+
+        for (start, target) in pairs {
+            assert_eq!(
+                scratch.edit_sequence(
+                    start,
+                    target,
+                    &Costs::default(),
+                    Anchor::default(),
+                    TieBreak::default(),
+                    None
+                ),
+                compute_edit_sequence(start, target),
+                "{start:?} -> {target:?}"
+            );
+        }
+    }
 }
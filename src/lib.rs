@@ -1,51 +1,1186 @@
-use std::collections::VecDeque;
+use std::{
+    collections::VecDeque,
+    ops::{Bound, Range, RangeBounds},
+    time::{Duration, Instant},
+};
 
-use crate::{edit::Edit, levenshtein::compute_edit_sequence};
+use smallstr::SmallString;
+use smallvec::SmallVec;
 
+use crate::{
+    cache::EditCache,
+    constraint::{
+        Plan, Validator, comply, crossfade, group_lines, group_words, minimize_jitter,
+        monotonic_length, prioritize_viewport,
+    },
+    edit::pack,
+    equivalence::{CharEquivalence, settle},
+    history::History,
+    levenshtein::{Scratch, compute_edit_sequence},
+    measure::{Measure, display_width},
+    playlist::Prepared,
+    queue::EditQueue,
+    rng::Rng,
+};
+
+mod anchor;
+#[cfg(feature = "figlet")]
+mod banner;
+mod cache;
+#[cfg(feature = "unicode-normalization")]
+mod canonical;
+mod canvas;
+mod chunk;
+mod churn;
+mod cleanup;
+mod confusables;
+mod constraint;
+mod cursor;
+mod describe;
+pub mod dmp;
 mod edit;
+mod effect;
+mod equivalence;
+mod frames;
+mod granularity;
+mod highlight;
+mod histogram;
+mod history;
 mod levenshtein;
+mod lines;
+pub mod lsp;
+mod measure;
+pub mod metrics;
+mod motion;
+mod number;
+pub mod ot;
+mod pacer;
+mod pacing;
+mod planner;
+mod playlist;
+mod position;
+mod profile;
+mod progress_bar;
+mod queue;
+mod receive;
+pub mod render;
+mod retarget;
+mod rng;
+mod schedule;
+mod scheduler;
+mod script;
+mod shared;
+#[cfg(feature = "similar")]
+mod similar;
+#[cfg(feature = "crossterm")]
+mod slideshow;
+mod stats;
+mod style;
+mod suggest;
+mod table;
+pub mod testing;
+mod text;
+mod tie_break;
+pub mod typestate;
+mod typing;
+#[cfg(feature = "tokio")]
+mod watch;
+#[cfg(feature = "web")]
+pub mod web;
+mod wrap;
+
+pub use anchor::Anchor;
+#[cfg(feature = "figlet")]
+pub use banner::MorphingBanner;
+pub use canvas::{CellChange, MorphingCanvas, Sweep};
+pub use churn::Churn;
+pub use constraint::WidthError;
+pub use cursor::CursorMorphingString;
+pub use edit::{Edit, PackedEdit};
+pub use effect::{Alphabet, Effect, LeetMap};
+pub use frames::{Frames, MorphingExt, MorphingFrames, morph_frames};
+pub use granularity::{AutoGranularity, Granularity};
+pub use highlight::Highlight;
+pub use histogram::HistogramPlanner;
+pub use levenshtein::{Costs, distance, distance_within};
+pub use lines::MorphingText;
+pub use motion::{reduced_motion, set_reduced_motion};
+pub use number::{MorphingNumber, NumberFormat};
+pub use pacer::FramePacer;
+pub use pacing::{Easing, EditWeights, Pacing};
+pub use planner::{LevenshteinPlanner, MorphPlanner};
+pub use playlist::Playlist;
+pub use position::LineColumn;
+pub use profile::Profile;
+pub use progress_bar::ProgressBar;
+pub use receive::ReceivingMorphingString;
+pub use retarget::RetargetPolicy;
+pub use scheduler::{MorphId, MorphScheduler};
+pub use script::{EditScript, Edits, IntoEdits, ScriptError};
+pub use shared::SharedMorphingString;
+#[cfg(feature = "crossterm")]
+pub use slideshow::Slideshow;
+pub use stats::{DiffStats, EditCounts, Stats};
+pub use style::MorphStyle;
+pub use suggest::{Suggestion, suggest};
+pub use table::{Alignment, MorphingTable};
+pub use text::EditableText;
+pub use tie_break::TieBreak;
+pub use typing::Typing;
+#[cfg(feature = "tokio")]
+pub use watch::WatchedMorphingString;
+pub use wrap::{Transition, WrappedMorphingString};
+
+#[cfg(feature = "derive")]
+pub use morphing_string_derive::Morphing;
+
+// Lets code generated by the derive macro refer to `::morphing_string` in this crate's own tests.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as morphing_string;
+
+/// Compute an [`EditScript`] that turns `start` into `target` with the fewest possible edits.
+pub fn diff(start: &str, target: &str) -> EditScript {
+    compute_edit_sequence(start, target).into()
+}
+
+/// [`diff`] every `(start, target)` pair. With the `rayon` feature enabled the pairs are diffed in
+/// parallel.
+pub fn diff_many(pairs: &[(String, String)]) -> Vec<EditScript> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
 
+        pairs
+            .par_iter()
+            .map(|(start, target)| diff(start, target))
+            .collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        pairs
+            .iter()
+            .map(|(start, target)| diff(start, target))
+            .collect()
+    }
+}
+
+/// Apply just the first of the fewest edits turning `current` into `target`, for callers keeping
+/// no state between frames. Calling it with every returned frame reaches `target` in [`distance`]
+/// calls, after which it keeps returning `target`.
+pub fn next_frame(current: &str, target: &str) -> String {
+    match compute_edit_sequence(current, target).front() {
+        Some(edit) => edit.apply(current),
+        None => current.to_string(),
+    }
+}
+
+// The edits applied by a single advance, kept inline as there are rarely more than a few.
+type Step = SmallVec<[Edit; 4]>;
+
+// The current value, kept inline while it's as short as most status lines.
+type Value = SmallString<[u8; 32]>;
+
+/// A string that morphs towards a target one [`Edit`] per [`MorphingString::advance`].
+///
+/// Edits only ever insert or substitute characters of the target, so every intermediate value
+/// consists solely of characters found in the value the morph started from or in the target. This
+/// makes morphs safe for displays that can only render a known set of glyphs. An [`Effect`] may
+/// introduce other glyphs, e.g. those of the [`Alphabet`] given to [`Effect::Scramble`].
 pub struct MorphingString {
-    current_value: String,
+    current_value: Value,
+    measure: Measure,
     target: String,
-    remaining_edits: VecDeque<Edit>,
+    remaining_edits: EditQueue,
+    // Number of edits applied by each of the upcoming advances. Empty if every advance applies a
+    // single edit. Otherwise adds up to the number of remaining edits.
+    remaining_steps: VecDeque<usize>,
+    // Edits applied by the last advance, usually just one.
+    last_step: Step,
     total_edits: usize,
+    total_counts: EditCounts,
+    costs: Costs,
+    anchor: Anchor,
+    tie_break: TieBreak,
+    cache: Option<EditCache>,
+    planner: Option<Box<dyn MorphPlanner + Send + Sync>>,
+    // Reused across plans so retargeting doesn't allocate the DP matrix every time.
+    scratch: Scratch,
+    effect: Option<Effect>,
+    rng: Rng,
+    validator: Option<Box<Validator>>,
+    max_width: Option<usize>,
+    char_equivalence: Option<Box<CharEquivalence>>,
+    monotonic_length: bool,
+    minimal_jitter: bool,
+    crossfade: bool,
+    // Char indexes of the value currently visible, whose edits get applied first.
+    viewport: Option<Range<usize>>,
+    granularity: Granularity,
+    semantic_cleanup: bool,
+    #[cfg(feature = "unicode-normalization")]
+    canonical_equivalence: bool,
+    reduced_motion: bool,
+    min_interval: Duration,
+    edit_weights: EditWeights,
+    last_advance: Option<Instant>,
+    retarget_policy: RetargetPolicy,
+    history: Option<History>,
+    // Edits planned ahead of time, e.g. by a playlist.
+    prepared: Option<Prepared>,
+    // Target to morph towards once the current morph completes.
+    queued_target: Option<String>,
 }
 
 impl MorphingString {
     pub fn new(value: String) -> Self {
         Self {
-            current_value: value.chars().collect(),
+            measure: Measure::of(&value),
+            current_value: Value::from(value.as_str()),
             target: value,
-            remaining_edits: VecDeque::new(),
+            remaining_edits: EditQueue::default(),
+            remaining_steps: VecDeque::new(),
+            last_step: Step::new(),
             total_edits: 0,
+            total_counts: EditCounts::default(),
+            costs: Costs::default(),
+            anchor: Anchor::default(),
+            tie_break: TieBreak::default(),
+            cache: None,
+            planner: None,
+            scratch: Scratch::default(),
+            effect: None,
+            rng: Rng::from_entropy(),
+            validator: None,
+            max_width: None,
+            char_equivalence: None,
+            monotonic_length: false,
+            minimal_jitter: false,
+            crossfade: false,
+            viewport: None,
+            granularity: Granularity::default(),
+            semantic_cleanup: false,
+            #[cfg(feature = "unicode-normalization")]
+            canonical_equivalence: false,
+            reduced_motion: false,
+            min_interval: Duration::ZERO,
+            edit_weights: EditWeights::default(),
+            last_advance: None,
+            retarget_policy: RetargetPolicy::default(),
+            history: None,
+            prepared: None,
+            queued_target: None,
         }
     }
 
+    /// Layer `effect` over the planned edits of every subsequent morph.
+    pub fn with_effect(mut self, effect: Effect) -> Self {
+        self.effect = Some(effect);
+        self
+    }
+
+    /// Require every intermediate value of subsequent morphs to satisfy `is_valid`.
+    ///
+    /// Edits are reordered, or applied several at once by a single [`MorphingString::advance`], so
+    /// that no rejected value is ever shown. In the worst case the whole morph happens in one step.
+    /// The target itself is never checked.
+    pub fn with_validator(
+        mut self,
+        is_valid: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.validator = Some(Box::new(is_valid));
+        self
+    }
+
+    /// Keep every intermediate value of subsequent morphs within `max_width` display columns, e.g.
+    /// the width of the terminal, so the value never wraps. Edits are reordered, e.g. deleting
+    /// chars before inserting others, or applied several at once like with
+    /// [`MorphingString::with_validator`]. Morphs that can't keep within it, e.g. because the
+    /// target or the current value is too wide, jump straight to the target instead. See
+    /// [`MorphingString::try_set_target`] for rejecting them.
+    pub fn with_max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Keep chars of the value that `equivalent` considers alike to those of the target instead of
+    /// morphing them, e.g. to ignore differences in dashes or let every digit stand in for any
+    /// other. Kept chars differing from the target are replaced in the last step so the morph
+    /// still ends with the exact target. Only applies to the default planner.
+    pub fn with_char_equivalence(
+        mut self,
+        equivalent: impl Fn(char, char) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.char_equivalence = Some(Box::new(equivalent));
+        self
+    }
+
+    /// Make the length of the value only ever move towards the length of the target, never
+    /// overshooting it or moving away from it, by applying insertions and deletions that would
+    /// oscillate the length together in a single step.
+    pub fn with_monotonic_length(mut self) -> Self {
+        self.monotonic_length = true;
+        self
+    }
+
+    /// Keep the chars of the value from shifting back and forth by applying insertions together
+    /// with the closest deletions in a single step.
+    pub fn with_minimal_jitter(mut self) -> Self {
+        self.minimal_jitter = true;
+        self
+    }
+
+    /// Spread deletions, insertions and substitutions evenly across the morph so the value appears
+    /// to crossfade from old to new rather than, say, first losing all of its chars.
+    pub fn with_crossfade(mut self) -> Self {
+        self.crossfade = true;
+        self
+    }
+
+    /// Apply all the edits within a word in a single [`MorphingString::advance`] so that no
+    /// half-changed word is ever shown. The edits themselves stay the same.
+    ///
+    /// Note that this also applies all the intermediate glyphs of an [`Effect`] at once.
+    pub fn with_word_grouping(mut self) -> Self {
+        self.granularity = Granularity::Word;
+        self
+    }
+
+    /// Plan morphs that read like intentional edits rather than scattered char noise, at the cost
+    /// of more edits. See [`EditScript::cleanup_semantic`].
+    pub fn with_semantic_cleanup(mut self) -> Self {
+        self.semantic_cleanup = true;
+        self
+    }
+
+    /// Choose the units every [`MorphingString::advance`] applies the edits of at once.
+    pub fn with_granularity(mut self, granularity: Granularity) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
+    /// Make [`MorphingString::advance_if_due`] advance at most once per `interval`.
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// Show every frame for as many intervals as the edits of the next step weigh, e.g. to linger
+    /// on insertions and rush through deletions. See [`MorphingString::next_step_duration`].
+    pub fn with_edit_weights(mut self, weights: EditWeights) -> Self {
+        self.edit_weights = weights;
+        self
+    }
+
+    /// Seed the randomness used by effects, making the morphs reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+
+    /// Remember the edits computed for up to `capacity` most recently used `(value, target)` pairs
+    /// so morphing back and forth between the same strings doesn't recompute them.
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = Some(EditCache::new(capacity));
+        self
+    }
+
+    /// Plan morphs minimizing the total `costs` of the edits rather than their number.
+    pub fn with_costs(mut self, costs: Costs) -> Self {
+        self.costs = costs;
+        self
+    }
+
+    /// Keep the given part of the value stable when there are several ways to morph it.
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Choose between equally cheap edits by `tie_break` rather than by the anchor.
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Compare chars by Unicode canonical equivalence when planning, e.g. so a composed `é` is kept
+    /// instead of being substituted by a decomposed one. Kept chars encoded differently than in the
+    /// target are re-encoded together with the last visible change, so the morph still ends with
+    /// the exact target.
+    #[cfg(feature = "unicode-normalization")]
+    pub fn with_canonical_equivalence(mut self) -> Self {
+        self.canonical_equivalence = true;
+        self
+    }
+
+    /// Jump straight to the target in a single advance, as every morph does after
+    /// [`set_reduced_motion`].
+    pub fn with_reduced_motion(mut self) -> Self {
+        self.reduced_motion = true;
+        self
+    }
+
+    /// Plan edits with `planner` instead of the [`LevenshteinPlanner`] configured by
+    /// [`MorphingString::with_costs`], [`MorphingString::with_anchor`] and
+    /// [`MorphingString::with_tie_break`].
+    pub fn with_planner(mut self, planner: impl MorphPlanner + Send + Sync + 'static) -> Self {
+        self.planner = Some(Box::new(planner));
+        self
+    }
+
+    /// Configure the costs and planning constraints to achieve a curated look. Replaces whatever
+    /// [`MorphingString::with_costs`], [`MorphingString::with_monotonic_length`] and
+    /// [`MorphingString::with_granularity`] configured before.
+    pub fn with_profile(mut self, profile: Profile) -> Self {
+        self.costs = Costs::default();
+        self.monotonic_length = false;
+        self.granularity = Granularity::Char;
+
+        match profile {
+            Profile::Minimal => {}
+            Profile::StableLength => self.monotonic_length = true,
+            Profile::FewestSubstitutions => {
+                // Pricier than a deletion and an insertion, so there are none.
+                self.costs.substitute = 3;
+            }
+            Profile::Blocky => self.granularity = Granularity::Word,
+        }
+        self
+    }
+
+    /// Choose what [`MorphingString::set_target`] does while a morph is in progress.
+    pub fn with_retarget_policy(mut self, policy: RetargetPolicy) -> Self {
+        self.retarget_policy = policy;
+        self
+    }
+
+    /// Like [`MorphingString::set_target`] but failing, without changing anything, if the morph
+    /// can't keep within the maximum width set by [`MorphingString::with_max_width`], e.g. because
+    /// the target or the value it starts from is too wide. A morph the [`RetargetPolicy`] queues
+    /// is checked from the value it starts from once dequeued.
+    pub fn try_set_target(&mut self, target: String) -> Result<(), WidthError> {
+        if self.max_width.is_some() {
+            let start = self.next_start();
+            let mut scratch = std::mem::take(&mut self.scratch);
+            let edits = self.compute_edits(&mut scratch, &start, &target);
+            self.scratch = scratch;
+            self.check_width(&start, &target, &edits)?;
+            self.prepare(Prepared {
+                start,
+                target: target.clone(),
+                edits,
+            });
+        }
+        self.set_target(target);
+        Ok(())
+    }
+
+    /// Morph towards `target` according to the [`RetargetPolicy`].
     pub fn set_target(&mut self, target: String) {
-        self.remaining_edits = compute_edit_sequence(&self.current_value, &target);
-        self.total_edits = self.remaining_edits.len();
+        if let Some(history) = &mut self.history {
+            history.push(target.clone());
+        }
+        self.retarget(target);
+    }
+
+    /// Morph towards `target` according to the [`RetargetPolicy`] without recording it in the
+    /// history.
+    fn retarget(&mut self, target: String) {
+        if self.remaining_edits.is_empty() {
+            return self.plan(target);
+        }
+
+        match self.retarget_policy {
+            RetargetPolicy::Replace => self.plan(target),
+            RetargetPolicy::Queue => self.queued_target = Some(target),
+            RetargetPolicy::Merge => {
+                self.truncate_remaining(self.word_in_progress());
+                if self.remaining_edits.is_empty() {
+                    self.plan(target);
+                } else {
+                    self.queued_target = Some(target);
+                }
+            }
+        }
+    }
+
+    /// The value the next morph starts from if [`MorphingString::retarget`] was called now: the
+    /// current value, or the value once the morph or the word it's in the middle of completes if
+    /// the [`RetargetPolicy`] lets it.
+    fn next_start(&self) -> String {
+        if self.remaining_edits.is_empty() {
+            return self.current_value.to_string();
+        }
+
+        match self.retarget_policy {
+            RetargetPolicy::Replace => self.current_value.to_string(),
+            RetargetPolicy::Queue => self.target.clone(),
+            RetargetPolicy::Merge => {
+                let keep = self.kept_edits(self.word_in_progress());
+                let mut start = self.current_value.to_string();
+                for edit in &self.remaining_edits[..keep] {
+                    edit.unpack().apply_in_place(&mut start);
+                }
+                start
+            }
+        }
+    }
+
+    /// The number of remaining edits completing the word in progress.
+    fn word_in_progress(&self) -> usize {
+        retarget::word_in_progress(
+            &self.current_value,
+            &self.last_step,
+            self.remaining_edits.iter().map(|edit| edit.unpack()),
+        )
+    }
+
+    /// Fail if the morph from `start` to `target` applying `edits` can't keep within the maximum
+    /// width, without changing anything.
+    fn check_width(
+        &mut self,
+        start: &str,
+        target: &str,
+        edits: &VecDeque<Edit>,
+    ) -> Result<(), WidthError> {
+        // Restored so the morph gets the same effect when it's scheduled for real.
+        let rng = self.rng.clone();
+        let scheduled = self.schedule(start, target, edits.clone());
+        self.rng = rng;
+        scheduled.map(drop).map_err(|(error, _)| error)
+    }
+
+    /// Extend the target with `suffix`, e.g. a token streamed in, appending the edits inserting it
+    /// to the remaining ones instead of planning the whole morph again. The appended edits get the
+    /// same effect, grouping, ordering and constraints as any planned ones. A queued target is
+    /// extended instead of the current one. The extended target replaces the current one in the
+    /// history.
+    pub fn append_to_target(&mut self, suffix: &str) {
+        if let Some(queued) = &mut self.queued_target {
+            queued.push_str(suffix);
+            if let Some(history) = &mut self.history {
+                history.replace_current(queued.clone());
+            }
+            return;
+        }
+
+        let mut target = std::mem::take(&mut self.target);
+        let start_len = target.chars().count();
+        let start = target.clone();
+        target.push_str(suffix);
+        let edits = (start_len..)
+            .zip(suffix.chars())
+            .map(|(index, c)| Edit::Insert { c, index })
+            .collect();
+        // Only the target can be too wide, which is what try_append_to_target checks.
+        let plan = self
+            .schedule(&start, &target, edits)
+            .unwrap_or_else(|(_, jump)| jump);
+
+        if !plan.steps.is_empty() && self.remaining_steps.is_empty() {
+            self.remaining_steps = std::iter::repeat_n(1, self.remaining_edits.len()).collect();
+        }
+        if plan.steps.is_empty() && !self.remaining_steps.is_empty() {
+            self.remaining_steps
+                .extend(std::iter::repeat_n(1, plan.edits.len()));
+        } else {
+            self.remaining_steps.extend(plan.steps);
+        }
+        self.total_edits += plan.edits.len();
+        for edit in &plan.edits {
+            self.total_counts.add(edit);
+        }
+        self.remaining_edits
+            .extend(plan.edits.into_iter().map(pack));
+        if let Some(history) = &mut self.history {
+            history.replace_current(target.clone());
+        }
         self.target = target;
     }
 
-    pub fn advance(&mut self) -> Progress {
-        if let Some(edit) = self.remaining_edits.pop_front() {
-            self.current_value = edit.apply(&self.current_value);
+    /// Like [`MorphingString::append_to_target`] but failing, without changing the target, if the
+    /// extended target is wider than the maximum set by [`MorphingString::with_max_width`].
+    pub fn try_append_to_target(&mut self, suffix: &str) -> Result<(), WidthError> {
+        if let Some(max_width) = self.max_width {
+            let target = self.queued_target.as_ref().unwrap_or(&self.target);
+            let width = display_width(target) + display_width(suffix);
+            if width > max_width {
+                return Err(WidthError { width, max_width });
+            }
+        }
+        self.append_to_target(suffix);
+        Ok(())
+    }
+
+    /// `keep` remaining edits rounded up to whole steps, like [`MorphingString::truncate_remaining`]
+    /// keeps.
+    fn kept_edits(&self, keep: usize) -> usize {
+        if self.remaining_steps.is_empty() {
+            return keep;
+        }
+        let mut kept_edits = 0;
+        for step in &self.remaining_steps {
+            if kept_edits >= keep {
+                break;
+            }
+            kept_edits += step;
+        }
+        kept_edits
+    }
+
+    /// Drop all but the first `keep` remaining edits, rounded up to whole steps.
+    fn truncate_remaining(&mut self, keep: usize) {
+        let mut kept_edits = 0;
+        if self.remaining_steps.is_empty() {
+            kept_edits = keep;
+        } else {
+            let mut kept_steps = 0;
+            while kept_edits < keep {
+                kept_edits += self.remaining_steps[kept_steps];
+                kept_steps += 1;
+            }
+            self.remaining_steps.truncate(kept_steps);
+        }
+
+        self.total_edits -= self.remaining_edits.len() - kept_edits;
+        for edit in &self.remaining_edits[kept_edits..] {
+            self.total_counts.remove(&edit.unpack());
+        }
+        self.remaining_edits.truncate(kept_edits);
+    }
+
+    /// Compute the edits towards `target` and start morphing.
+    fn plan(&mut self, target: String) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "set_target",
+            value_len = self.current_value.chars().count(),
+            target_len = target.chars().count(),
+            edits = tracing::field::Empty,
+        )
+        .entered();
+        #[cfg(feature = "metrics")]
+        let planning_started = Instant::now();
+
+        let edits = match self.prepared.take() {
+            Some(prepared)
+                if prepared.start == self.current_value.as_str() && prepared.target == target =>
+            {
+                prepared.edits
+            }
+            _ => {
+                let mut scratch = std::mem::take(&mut self.scratch);
+                let mut cache = self.cache.take();
+                let mut compute =
+                    |start: &str, target: &str| self.compute_edits(&mut scratch, start, target);
+                let edits = match &mut cache {
+                    Some(cache) => cache.get_or_compute(&self.current_value, &target, compute),
+                    None => compute(&self.current_value, &target),
+                };
+                self.scratch = scratch;
+                self.cache = cache;
+                edits
+            }
         };
+        self.start_morph(target, edits);
+        if self.char_equivalence.is_some() {
+            self.settle_equivalent_chars();
+        }
+        #[cfg(feature = "unicode-normalization")]
+        if self.canonical_equivalence {
+            let remaining = self.remaining_edits.iter().map(|edit| edit.unpack());
+            let visible = canonical::visible_edits(&self.current_value, remaining);
+            self.merge_steps_from(visible.saturating_sub(1));
+        }
+
+        #[cfg(feature = "metrics")]
+        ::metrics::histogram!("morphing_string_planning_seconds")
+            .record(planning_started.elapsed().as_secs_f64());
+    }
+
+    /// The edits from `start` to `target` as planned by the configured planner, costs, equivalences
+    /// and cleanup, bypassing the cache.
+    pub(crate) fn compute_edits(
+        &self,
+        scratch: &mut Scratch,
+        start: &str,
+        target: &str,
+    ) -> VecDeque<Edit> {
+        let equivalent = self.char_equivalence.as_deref();
+        // Canonical equivalence plans with stand-ins for the chars, which can't be compared.
+        #[cfg(feature = "unicode-normalization")]
+        let equivalent = equivalent.filter(|_| !self.canonical_equivalence);
+        let mut plan = |start: &str, target: &str| match &self.planner {
+            Some(planner) => planner.plan(start, target).into(),
+            None => scratch.edit_sequence(
+                start,
+                target,
+                &self.costs,
+                self.anchor,
+                self.tie_break,
+                equivalent,
+            ),
+        };
+        #[cfg(feature = "unicode-normalization")]
+        let edits = if self.canonical_equivalence {
+            canonical::plan(start, target, plan)
+        } else {
+            plan(start, target)
+        };
+        #[cfg(not(feature = "unicode-normalization"))]
+        let edits = plan(start, target);
+
+        if self.semantic_cleanup {
+            return cleanup::cleanup_edits(start, edits.iter().copied());
+        }
+        edits
+    }
+
+    /// Use `prepared` edits instead of planning if the next planned morph goes from its start to
+    /// its target.
+    pub(crate) fn prepare(&mut self, prepared: Prepared) {
+        self.prepared = Some(prepared);
+    }
+
+    /// Replace the chars kept for being equivalent to those of the target together with the last
+    /// remaining step.
+    fn settle_equivalent_chars(&mut self) {
+        let remaining = self.remaining_edits.iter().map(|edit| edit.unpack());
+        let settling = settle(&self.current_value, remaining, &self.target);
+        if settling.is_empty() {
+            return;
+        }
+
+        let visible = self.remaining_edits.len();
+        for edit in settling {
+            self.remaining_edits.push_back(pack(edit));
+            if !self.remaining_steps.is_empty() {
+                self.remaining_steps.push_back(1);
+            }
+            self.total_edits += 1;
+            self.total_counts.add(&edit);
+        }
+        self.merge_steps_from(visible.saturating_sub(1));
+    }
+
+    /// Apply all remaining edits from the `from`-th on, and any others in the step it falls in, in
+    /// a single step.
+    fn merge_steps_from(&mut self, from: usize) {
+        let len = self.remaining_edits.len();
+        if from + 1 >= len {
+            return;
+        }
+        if self.remaining_steps.is_empty() {
+            self.remaining_steps = std::iter::repeat_n(1, len).collect();
+        }
+
+        let mut merged_from = 0;
+        let mut kept_steps = 0;
+        while merged_from + self.remaining_steps[kept_steps] <= from {
+            merged_from += self.remaining_steps[kept_steps];
+            kept_steps += 1;
+        }
+        self.remaining_steps.truncate(kept_steps);
+        self.remaining_steps.push_back(len - merged_from);
+    }
+
+    /// Morph towards `target` by applying `edits`, e.g. precomputed or hand-crafted ones, instead
+    /// of planning them. The edits replace any morph in progress and must turn the current value
+    /// into `target`. Effects, granularity and constraints still apply to them. Fails without
+    /// changing anything if they don't, or if they can't keep within the maximum width set by
+    /// [`MorphingString::with_max_width`].
+    pub fn set_edits(&mut self, target: String, edits: EditScript) -> Result<(), ScriptError> {
+        edits.check(&self.current_value, &target)?;
+        let edits = edits.into();
+        if self.max_width.is_some() {
+            let start = self.current_value.to_string();
+            self.check_width(&start, &target, &edits)
+                .map_err(ScriptError::TooWide)?;
+        }
+        self.start_morph(target, edits);
+        Ok(())
+    }
+
+    /// Morph towards whichever of `candidates` is the fewest edits away from the current value and
+    /// return its index. The first candidate wins ties. Returns `None` if there are no candidates.
+    pub fn set_nearest_target(&mut self, candidates: &[String]) -> Option<usize> {
+        let (nearest, _) = candidates
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, candidate)| distance(&self.current_value, candidate))?;
+
+        self.set_target(candidates[nearest].clone());
+        Some(nearest)
+    }
+
+    /// Hint that only the chars at `viewport`, a range of char indexes of the value, are visible,
+    /// e.g. of a long line scrolled horizontally. Edits within it are applied first, including
+    /// the remaining ones of the current morph, and off-screen ones last.
+    pub fn set_viewport(&mut self, viewport: Range<usize>) {
+        if !self.remaining_edits.is_empty() {
+            let edits = self
+                .remaining_edits
+                .iter()
+                .map(|edit| edit.unpack())
+                .collect();
+            let mut plan = match std::mem::take(&mut self.remaining_steps) {
+                steps if steps.is_empty() => Plan::single_steps(edits),
+                steps => Plan { edits, steps },
+            };
+            plan = prioritize_viewport(&self.current_value, plan, &viewport);
+            plan = self
+                .comply(&self.current_value, plan)
+                .unwrap_or_else(|(_, jump)| jump);
+            self.remaining_edits
+                .replace(plan.edits.into_iter().map(pack));
+            self.remaining_steps = plan.steps;
+        }
+        self.viewport = Some(viewport);
+    }
+
+    /// Forget the viewport set by [`MorphingString::set_viewport`], applying edits in the planned
+    /// order again from the next target on.
+    pub fn clear_viewport(&mut self) {
+        self.viewport = None;
+    }
+
+    /// Start morphing towards `target` using already computed `edits`, jumping straight to it if
+    /// the morph can't keep within the maximum width.
+    fn start_morph(&mut self, target: String, edits: VecDeque<Edit>) {
+        self.queued_target = None;
+        let start = std::mem::take(&mut self.current_value);
+        let mut plan = self
+            .schedule(&start, &target, edits)
+            .unwrap_or_else(|(_, jump)| jump);
+        self.current_value = start;
+        self.total_edits = plan.edits.len();
+        self.total_counts = plan.edits.iter().collect();
+        self.remaining_edits.replace(plan.edits.drain(..).map(pack));
+        self.scratch.recycle(plan.edits);
+        self.remaining_steps = plan.steps;
+        self.target = target;
+
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("edits", self.total_edits);
+        #[cfg(feature = "metrics")]
+        ::metrics::histogram!("morphing_string_script_length").record(self.total_edits as f64);
+    }
+
+    /// Layer the effect over `edits` turning `start` into `target`, then group, order and
+    /// constrain them into steps. No steps mean one edit per step. Fails with a plan jumping
+    /// straight to the target if the maximum width can't be respected.
+    fn schedule(
+        &mut self,
+        start: &str,
+        target: &str,
+        edits: VecDeque<Edit>,
+    ) -> Result<Plan, (WidthError, Plan)> {
+        let granularity = self.granularity.resolve(start, target, edits.len());
+        let edits = match &self.effect {
+            Some(effect) => effect.apply(start, edits, &mut self.rng),
+            None => edits,
+        };
+        if self.validator.is_none()
+            && self.max_width.is_none()
+            && !self.monotonic_length
+            && !self.minimal_jitter
+            && !self.crossfade
+            && self.viewport.is_none()
+            && granularity == Granularity::Char
+        {
+            return Ok(Plan {
+                edits,
+                steps: VecDeque::new(),
+            });
+        }
+
+        let mut plan = Plan::single_steps(edits);
+        match granularity {
+            Granularity::Word => plan = group_words(start, plan),
+            Granularity::Line => plan = group_lines(start, plan),
+            Granularity::Char | Granularity::Auto(_) => {}
+        }
+        if self.crossfade {
+            plan = crossfade(start, plan);
+        }
+        if self.minimal_jitter {
+            plan = minimize_jitter(start, plan);
+        }
+        if self.monotonic_length {
+            plan = monotonic_length(start, plan);
+        }
+        if let Some(viewport) = &self.viewport {
+            plan = prioritize_viewport(start, plan, viewport);
+        }
+        self.comply(start, plan)
+    }
+
+    /// Make `plan` from `start` comply with the validator and the maximum width, if any. Fails
+    /// with a plan jumping straight to the target if the maximum width can't be respected.
+    fn comply(&self, start: &str, plan: Plan) -> Result<Plan, (WidthError, Plan)> {
+        if self.validator.is_none() && self.max_width.is_none() {
+            return Ok(plan);
+        }
+        let is_valid = self
+            .validator
+            .as_deref()
+            .map(|is_valid| is_valid as &dyn Fn(&str) -> bool);
+        comply(start, &plan, self.max_width, is_valid)
+            .map_err(|error| (error, Plan::jump(plan.edits)))
+    }
+
+    pub fn advance(&mut self) -> Progress {
+        let step = self.next_step();
+        if step == self.remaining_edits.len() {
+            self.remaining_steps.clear();
+        } else {
+            self.remaining_steps.pop_front();
+        }
+        self.last_step.clear();
+        for edit in self
+            .remaining_edits
+            .take_front(step)
+            .map(PackedEdit::unpack)
+        {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(?edit, "applied edit");
+
+            assert!(edit.index() <= self.measure.chars, "edit is in bounds");
+            let offset = self.measure.byte_offset(&self.current_value, edit.index());
+            let removed = edit.apply_at(&mut self.current_value, offset);
+            self.measure.update(&edit, removed);
+            self.last_step.push(edit);
+        }
+
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!("morphing_string_edits_applied").increment(self.last_step.len() as u64);
+
+        #[cfg(any(feature = "tracing", feature = "metrics"))]
+        if !self.last_step.is_empty() && self.remaining_edits.is_empty() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(edits = self.total_edits, "morph complete");
+            #[cfg(feature = "metrics")]
+            ::metrics::counter!("morphing_string_morphs_completed").increment(1);
+        }
+
+        if self.remaining_edits.is_empty()
+            && let Some(target) = self.queued_target.take()
+        {
+            self.plan(target);
+        }
 
         self.progress()
     }
 
-    pub fn value(&self) -> &String {
+    /// The number of edits the next advance applies.
+    fn next_step(&self) -> usize {
+        if self.reduced_motion || reduced_motion() {
+            self.remaining_edits.len()
+        } else {
+            self.remaining_steps.front().copied().unwrap_or(1)
+        }
+    }
+
+    /// How long to show the current value before the next advance when advancing every
+    /// `interval`: the interval times the weight of the next step, that of its heaviest edit. See
+    /// [`MorphingString::with_edit_weights`].
+    pub fn next_step_duration(&self, interval: Duration) -> Duration {
+        interval * self.next_step_weight()
+    }
+
+    pub(crate) fn next_step_weight(&self) -> u32 {
+        let step = self.next_step().min(self.remaining_edits.len());
+        let edits = &self.remaining_edits[..step];
+        self.edit_weights
+            .step(edits.iter().map(|edit| edit.unpack()))
+    }
+
+    /// The edits the last [`MorphingString::advance`] applied, in order. Their indexes are relative
+    /// to the value as it evolved during the advance.
+    pub fn last_edits(&self) -> &[Edit] {
+        &self.last_step
+    }
+
+    /// How every char of the current value was changed by the last advance or is about to be
+    /// changed by the next one. Lets renderers highlight changes.
+    pub fn highlights(&self) -> Vec<Option<Highlight>> {
+        let next_step = self.next_step();
+        highlight::highlights(
+            self.current_value.chars().count(),
+            &self.last_step,
+            self.remaining_edits
+                .iter()
+                .take(next_step)
+                .map(|edit| edit.unpack()),
+        )
+    }
+
+    /// The indexes of all the chars of the current value that the remaining edits substitute or
+    /// delete, in ascending order. Lets a UI preview everything that's going to change.
+    pub fn pending_positions(&self) -> impl Iterator<Item = usize> {
+        let remaining = self.remaining_edits.iter().map(|edit| edit.unpack());
+        highlight::touched(self.current_value.chars().count(), remaining)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, touched)| touched.then_some(index))
+    }
+
+    /// Advance unless less than the configured minimum interval passed since the last time this
+    /// advanced, in which case nothing happens. Lets a fast render loop drive a slower morph.
+    pub fn advance_if_due(&mut self, now: Instant) -> Progress {
+        let is_due = self.last_advance.is_none_or(|last| {
+            now.saturating_duration_since(last) >= self.next_step_duration(self.min_interval)
+        });
+
+        if !is_due {
+            return self.progress();
+        }
+
+        self.last_advance = Some(now);
+        self.advance()
+    }
+
+    /// The current, possibly intermediate, value. Borrowed so reading it never allocates.
+    pub fn value(&self) -> &str {
         &self.current_value
     }
 
+    /// The `index`-th char of the current value, if there's one.
+    pub fn char_at(&self, index: usize) -> Option<char> {
+        self.current_value[self.byte_offset(index)..].chars().next()
+    }
+
+    /// The chars of the current value in `range` of char indexes, e.g. for drawing just the visible
+    /// part of a long value. The range is clamped to the value.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> &str {
+        let end = match range.end_bound() {
+            Bound::Included(end) => end.saturating_add(1),
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.measure.chars,
+        }
+        .min(self.measure.chars);
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        }
+        .min(end);
+
+        &self.current_value[self.byte_offset(start)..self.byte_offset(end)]
+    }
+
+    /// The byte offset of the `index`-th char of the current value, or its length past the end.
+    fn byte_offset(&self, index: usize) -> usize {
+        self.measure.byte_offset(&self.current_value, index)
+    }
+
+    /// The number of chars of the current value, kept up to date as edits apply.
+    pub fn char_count(&self) -> usize {
+        self.measure.chars
+    }
+
+    /// The display width of the current value in terminal columns, summing the widths of its chars
+    /// as they're edited instead of measuring the whole value again. Sequences joined into a single
+    /// glyph, like emoji with zero width joiners, count as the sum of their chars. The maximum
+    /// width of [`MorphingString::with_max_width`] is measured the same way.
+    pub fn display_width(&self) -> usize {
+        self.measure.width
+    }
+
     pub fn progress(&self) -> Progress {
         Progress {
             total_edits: self.total_edits,
             remaining_edits: self.remaining_edits.len(),
         }
     }
+
+    /// Like [`MorphingString::progress`] but counting inserts, deletes and substitutions
+    /// separately.
+    pub fn stats(&self) -> Stats {
+        Stats {
+            total: self.total_counts,
+            remaining: self
+                .remaining_edits
+                .iter()
+                .map(|edit| edit.unpack())
+                .collect(),
+        }
+    }
+
+    /// Capture the current value, target and remaining edits, e.g. to speculatively advance and
+    /// roll back later. The configuration, like the effect or validator, isn't part of it.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            current_value: self.current_value.clone(),
+            target: self.target.clone(),
+            remaining_edits: self.remaining_edits.clone(),
+            remaining_steps: self.remaining_steps.clone(),
+            last_step: self.last_step.clone(),
+            total_edits: self.total_edits,
+            total_counts: self.total_counts,
+            rng: self.rng.clone(),
+            queued_target: self.queued_target.clone(),
+        }
+    }
+
+    /// Return to the state captured by `snapshot`.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        let Snapshot {
+            current_value,
+            target,
+            remaining_edits,
+            remaining_steps,
+            last_step,
+            total_edits,
+            total_counts,
+            rng,
+            queued_target,
+        } = snapshot;
+
+        self.measure = Measure::of(&current_value);
+        self.current_value = current_value;
+        self.target = target;
+        self.remaining_edits = remaining_edits;
+        self.remaining_steps = remaining_steps;
+        self.last_step = last_step;
+        self.total_edits = total_edits;
+        self.total_counts = total_counts;
+        self.rng = rng;
+        self.queued_target = queued_target;
+    }
+}
+
+/// Compares the current value.
+impl PartialEq<str> for MorphingString {
+    fn eq(&self, other: &str) -> bool {
+        self.current_value.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for MorphingString {
+    fn eq(&self, other: &&str) -> bool {
+        self.current_value.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for MorphingString {
+    fn eq(&self, other: &String) -> bool {
+        self.current_value.as_str() == *other
+    }
+}
+
+/// The state of a morph at some point, restorable with [`MorphingString::restore`].
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    current_value: Value,
+    target: String,
+    remaining_edits: EditQueue,
+    remaining_steps: VecDeque<usize>,
+    last_step: Step,
+    total_edits: usize,
+    total_counts: EditCounts,
+    rng: Rng,
+    queued_target: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -62,8 +1197,25 @@ impl Progress {
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
     use super::*;
 
+    #[test]
+    fn next_frame_steps_towards_the_target() {
+        let mut frame = "kitten".to_string();
+        for _ in 0..distance("kitten", "sitting") {
+            assert_ne!(frame, "sitting");
+            frame = next_frame(&frame, "sitting");
+        }
+
+        assert_eq!(frame, "sitting");
+        assert_eq!(next_frame(&frame, "sitting"), "sitting");
+    }
+
     #[test]
     fn it_works() {
         let mut string = MorphingString::new("abcd".to_string());
@@ -75,4 +1227,629 @@ mod tests {
 
         assert_eq!(string.value(), "1234");
     }
+
+    #[test]
+    fn intermediates_only_contain_start_or_target_chars() {
+        let pairs = [
+            ("kitten", "mittens"),
+            ("12:59", "13:00"),
+            ("daskdas dasd sadjasnd", "nfad ad f sasdkmfpsmdfasM"),
+        ];
+
+        for (start, target) in pairs {
+            let mut string = MorphingString::new(start.to_string());
+            string.set_target(target.to_string());
+
+            while !string.advance().is_complete() {
+                assert!(
+                    string
+                        .value()
+                        .chars()
+                        .all(|c| start.contains(c) || target.contains(c)),
+                    "{start:?} -> {target:?}: intermediate {:?}",
+                    string.value()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn scrambled_morph_reaches_target() {
+        let mut string = MorphingString::new("kitten".to_string())
+            .with_effect(Effect::Scramble {
+                steps: 2,
+                alphabet: Alphabet::new("アイウエオ"),
+            })
+            .with_seed(3);
+        string.set_target("mittens".to_string());
+
+        assert_eq!(string.progress().total_edits, 2 * 3);
+        while !string.advance().is_complete() {
+            assert!(
+                string
+                    .value()
+                    .chars()
+                    .all(|c| "kittenms".contains(c) || "アイウエオ".contains(c))
+            );
+        }
+        assert_eq!(string.value(), "mittens");
+    }
+
+    #[test]
+    fn validated_morph_never_shows_rejected_values() {
+        let mut string = MorphingString::new("kitten".to_string())
+            .with_validator(|value| !value.contains("mit"));
+        string.set_target("mittens".to_string());
+
+        while !string.advance().is_complete() {
+            assert!(!string.value().contains("mit"), "{}", string.value());
+        }
+        assert_eq!(string.value(), "mittens");
+    }
+
+    #[test]
+    fn monotonic_morph_never_overshoots_target_length() {
+        let mut string = MorphingString::new("abcdef".to_string()).with_monotonic_length();
+        string.set_target("xazced".to_string());
+
+        while !string.advance().is_complete() {
+            assert_eq!(string.value().chars().count(), 6, "{}", string.value());
+        }
+        assert_eq!(string.value(), "xazced");
+    }
+
+    #[test]
+    fn equivalent_chars_are_kept_until_the_end() {
+        let mut string = MorphingString::new("price: 10".to_string())
+            .with_char_equivalence(|a, b| a.is_ascii_digit() && b.is_ascii_digit());
+        string.set_target("cost: 25".to_string());
+
+        while !string.advance().is_complete() {
+            assert!(string.value().ends_with(": 10"), "{}", string.value());
+        }
+        assert_eq!(string.value(), "cost: 25");
+    }
+
+    #[test]
+    fn intermediates_stay_within_max_width() {
+        let mut unconstrained = MorphingString::new("abcd".to_string());
+        unconstrained.set_target("xabc".to_string());
+        assert_eq!(unconstrained.advance().remaining_edits, 1);
+        assert_eq!(unconstrained.value(), "xabcd");
+
+        let mut string = MorphingString::new("abcd".to_string()).with_max_width(4);
+        string.set_target("xabc".to_string());
+        while !string.advance().is_complete() {
+            assert!(display_width(string.value()) <= 4, "{}", string.value());
+        }
+        assert_eq!(string.value(), "xabc");
+
+        assert_eq!(
+            string.try_set_target("wider".to_string()),
+            Err(WidthError {
+                width: 5,
+                max_width: 4
+            })
+        );
+        assert_eq!(string.target, "xabc");
+    }
+
+    #[test]
+    fn values_already_too_wide_jump_to_the_target() {
+        let mut string = MorphingString::new("abcdef".to_string()).with_max_width(4);
+
+        assert_eq!(
+            string.try_set_target("abc".to_string()),
+            Err(WidthError {
+                width: 6,
+                max_width: 4
+            })
+        );
+        assert_eq!(string.target, "abcdef");
+
+        string.set_target("abc".to_string());
+        assert!(string.advance().is_complete());
+        assert_eq!(string.value(), "abc");
+    }
+
+    #[test]
+    fn queued_targets_are_checked_from_the_current_target() {
+        let mut string = MorphingString::new("ab".to_string())
+            .with_max_width(4)
+            .with_retarget_policy(RetargetPolicy::Queue);
+        string.set_target("abcdefgh".to_string());
+
+        // "xy" fits, but the queued morph would start from the current target.
+        assert_eq!(
+            string.try_set_target("xy".to_string()),
+            Err(WidthError {
+                width: 8,
+                max_width: 4
+            })
+        );
+        assert_eq!(string.queued_target, None);
+
+        let mut string = MorphingString::new("ab".to_string())
+            .with_max_width(4)
+            .with_retarget_policy(RetargetPolicy::Queue);
+        string.set_target("abcd".to_string());
+        assert_eq!(string.try_set_target("xy".to_string()), Ok(()));
+        assert_eq!(string.queued_target.as_deref(), Some("xy"));
+    }
+
+    #[test]
+    fn visible_edits_are_applied_first() {
+        let mut string = MorphingString::new("aaaa bbbb".to_string());
+        string.set_target("xxxx yyyy".to_string());
+        string.set_viewport(5..9);
+
+        for _ in 0..4 {
+            string.advance();
+        }
+        assert_eq!(string.value(), "aaaa yyyy");
+        while !string.advance().is_complete() {}
+        assert_eq!(string.value(), "xxxx yyyy");
+    }
+
+    #[test]
+    fn word_grouped_morph_advances_by_words() {
+        let mut string = MorphingString::new("the quick fox".to_string()).with_word_grouping();
+        string.set_target("a quack box".to_string());
+
+        assert_eq!(string.advance().remaining_edits, 2);
+        assert_eq!(string.value(), "a quick fox");
+        assert!(!string.advance().is_complete());
+        assert!(string.advance().is_complete());
+        assert_eq!(string.value(), "a quack box");
+    }
+
+    #[test]
+    fn advance_if_due_throttles() {
+        let mut string =
+            MorphingString::new("abcd".to_string()).with_min_interval(Duration::from_millis(30));
+        string.set_target("1234".to_string());
+        let start = Instant::now();
+
+        let mut remaining = |ms| {
+            string
+                .advance_if_due(start + Duration::from_millis(ms))
+                .remaining_edits
+        };
+        assert_eq!(remaining(0), 3);
+        assert_eq!(remaining(8), 3);
+        assert_eq!(remaining(16), 3);
+        assert_eq!(remaining(30), 2);
+        assert_eq!(remaining(40), 2);
+        assert_eq!(remaining(60), 1);
+    }
+
+    #[test]
+    fn edit_weights_stretch_steps() {
+        let mut morph = MorphingString::new("ab".to_string()).with_edit_weights(EditWeights {
+            insert: 2,
+            delete: 1,
+            substitute: 3,
+        });
+        morph.set_target("xbc".to_string());
+        let interval = Duration::from_millis(10);
+
+        let mut durations = vec![morph.next_step_duration(interval)];
+        while !morph.advance().is_complete() {
+            durations.push(morph.next_step_duration(interval));
+        }
+
+        assert_eq!(
+            durations,
+            [Duration::from_millis(30), Duration::from_millis(20)]
+        );
+    }
+
+    #[test]
+    fn nearest_target_is_chosen() {
+        let mut string = MorphingString::new("kitten".to_string());
+        let candidates = ["sitting", "mittens", "kitchen"].map(String::from);
+
+        assert_eq!(string.set_nearest_target(&candidates), Some(1));
+        while !string.advance().is_complete() {}
+        assert_eq!(string.value(), "mittens");
+
+        assert_eq!(string.set_nearest_target(&[]), None);
+    }
+
+    #[test]
+    fn diff_many_diffs_every_pair() {
+        let pairs = [("kitten", "mittens"), ("", "abc"), ("sunday", "saturday")]
+            .map(|(start, target)| (start.to_string(), target.to_string()));
+
+        let scripts = diff_many(&pairs);
+
+        assert_eq!(scripts.len(), pairs.len());
+        for ((start, target), script) in pairs.iter().zip(scripts) {
+            assert_eq!(script, diff(start, target));
+        }
+    }
+
+    #[test]
+    fn cached_morph_reaches_target() {
+        let mut string = MorphingString::new("abcd".to_string()).with_cache(4);
+
+        for target in ["1234", "abcd", "1234"] {
+            string.set_target(target.to_string());
+            while !string.advance().is_complete() {}
+            assert_eq!(string.value(), target);
+        }
+    }
+
+    fn run(morph: &mut MorphingString) -> Vec<String> {
+        let mut frames = vec![morph.value().to_string()];
+        while !morph.advance().is_complete() {
+            frames.push(morph.value().to_string());
+        }
+        frames.push(morph.value().to_string());
+        frames
+    }
+
+    #[test]
+    fn queued_target_follows_current_morph() {
+        let mut morph =
+            MorphingString::new("ab".to_string()).with_retarget_policy(RetargetPolicy::Queue);
+        morph.set_target("xy".to_string());
+        morph.advance();
+        morph.set_target("xz".to_string());
+
+        assert_eq!(run(&mut morph), vec!["xb", "xy", "xz"]);
+    }
+
+    #[test]
+    fn merge_finishes_the_word_in_progress() {
+        let mut morph =
+            MorphingString::new("ab cd".to_string()).with_retarget_policy(RetargetPolicy::Merge);
+        morph.set_target("xy zw".to_string());
+        morph.advance();
+        morph.set_target("xy cd".to_string());
+
+        assert_eq!(run(&mut morph), vec!["xb cd", "xy cd"]);
+    }
+
+    #[test]
+    fn custom_planner_plans_the_morph() {
+        let retype = |from: &str, to: &str| -> EditScript {
+            let deletes = from.chars().map(|_| Edit::Delete { index: 0 });
+            let inserts = (to.chars().enumerate()).map(|(index, c)| Edit::Insert { c, index });
+            deletes.chain(inserts).collect()
+        };
+        let mut morph = MorphingString::new("ab".to_string()).with_planner(retype);
+        morph.set_target("ac".to_string());
+
+        assert_eq!(run(&mut morph), vec!["ab", "b", "", "a", "ac"]);
+    }
+
+    #[test]
+    fn reduced_motion_jumps_to_the_target() {
+        let mut morph = MorphingString::new("kitten".to_string())
+            .with_granularity(Granularity::Word)
+            .with_reduced_motion();
+        morph.set_target("sitting".to_string());
+
+        assert_eq!(run(&mut morph), vec!["kitten", "sitting"]);
+    }
+
+    #[test]
+    fn compares_with_the_current_value() {
+        let mut morph = MorphingString::new("ab".to_string());
+        let target = String::from("b");
+        morph.set_target(target.clone());
+
+        assert!(morph == "ab");
+        assert!(morph != target);
+        morph.advance();
+        assert!(morph == *"b");
+    }
+
+    #[test]
+    fn appended_suffix_is_inserted_after_the_target() {
+        let mut morph = MorphingString::new("ab".to_string());
+        morph.set_target("b".to_string());
+        morph.advance();
+        morph.append_to_target("cd");
+
+        assert_eq!(run(&mut morph), vec!["b", "bc", "bcd"]);
+        assert_eq!(morph.stats().total.inserts, 2);
+        morph.append_to_target("e");
+        assert_eq!(run(&mut morph), vec!["bcd", "bcde"]);
+    }
+
+    #[test]
+    fn max_width_measures_joined_sequences_like_display_width() {
+        let target = "👩\u{200d}💻";
+        let width = display_width(target);
+        let mut morph = MorphingString::new(String::new()).with_max_width(width - 1);
+
+        assert_eq!(
+            morph.try_set_target(target.to_string()),
+            Err(WidthError {
+                width,
+                max_width: width - 1
+            })
+        );
+    }
+
+    #[test]
+    fn appended_suffix_respects_max_width() {
+        let mut morph = MorphingString::new("ab".to_string()).with_max_width(4);
+        morph.set_target("a".to_string());
+
+        assert_eq!(
+            morph.try_append_to_target("bcde"),
+            Err(WidthError {
+                width: 5,
+                max_width: 4
+            })
+        );
+        assert_eq!(morph.target, "a");
+        assert_eq!(morph.try_append_to_target("bcd"), Ok(()));
+        assert_eq!(run(&mut morph), vec!["ab", "a", "ab", "abc", "abcd"]);
+    }
+
+    #[test]
+    fn appended_suffix_is_grouped_and_recorded() {
+        let mut morph = MorphingString::new(String::new())
+            .with_granularity(Granularity::Word)
+            .with_history(4);
+        morph.set_target("one".to_string());
+        morph.append_to_target(" two");
+
+        assert_eq!(run(&mut morph), vec!["", "one", "one ", "one two"]);
+        morph.set_target("three".to_string());
+        while !morph.advance().is_complete() {}
+        assert!(morph.back());
+        assert_eq!(morph.target, "one two");
+    }
+
+    #[test]
+    fn set_edits_drives_the_morph() {
+        let mut morph = MorphingString::new("ab".to_string());
+        let edits = [
+            Edit::Delete { index: 0 },
+            Edit::Delete { index: 0 },
+            Edit::Insert { c: 'b', index: 0 },
+            Edit::Insert { c: 'a', index: 0 },
+        ];
+        morph
+            .set_edits("ab".to_string(), edits.into_iter().collect())
+            .unwrap();
+
+        assert_eq!(run(&mut morph), vec!["ab", "b", "", "b", "ab"]);
+    }
+
+    #[test]
+    fn set_edits_rejects_invalid_scripts() {
+        let mut morph = MorphingString::new("ab".to_string());
+        let out_of_bounds = [Edit::Delete { index: 2 }].into_iter().collect();
+        let wrong_target = [Edit::Delete { index: 1 }].into_iter().collect();
+
+        assert_eq!(
+            morph.set_edits("a".to_string(), out_of_bounds),
+            Err(ScriptError::OutOfBounds { position: 0 })
+        );
+        assert_eq!(
+            morph.set_edits("b".to_string(), wrong_target),
+            Err(ScriptError::WrongTarget)
+        );
+        assert_eq!(morph.value(), "ab");
+        assert!(morph.advance().is_complete());
+    }
+
+    #[test]
+    fn set_edits_rejects_scripts_overshooting_the_max_width() {
+        let mut morph = MorphingString::new("a".to_string()).with_max_width(1);
+        // The wide char can't be skipped, the second substitution overwrites it.
+        let overshooting = [
+            Edit::Substitute { c: '你', index: 0 },
+            Edit::Substitute { c: 'b', index: 0 },
+        ];
+
+        assert_eq!(
+            morph.set_edits("b".to_string(), overshooting.into_iter().collect()),
+            Err(ScriptError::TooWide(WidthError {
+                width: display_width("你"),
+                max_width: 1
+            }))
+        );
+        assert_eq!(morph.target, "a");
+        assert!(morph.advance().is_complete());
+    }
+
+    #[test]
+    fn restore_rolls_back_speculative_advances() {
+        let mut morph = MorphingString::new("kitten".to_string());
+        morph.set_target("sitting".to_string());
+        morph.advance();
+        let snapshot = morph.snapshot();
+
+        morph.set_target("mittens".to_string());
+        while !morph.advance().is_complete() {}
+        morph.restore(snapshot);
+
+        assert_eq!(morph.value(), "sitten");
+        assert_eq!(run(&mut morph).last().unwrap(), "sitting");
+    }
+
+    #[test]
+    fn stats_count_edit_kinds() {
+        let mut morph = MorphingString::new("kitten".to_string());
+        morph.set_target("sitting".to_string());
+        morph.advance();
+
+        let stats = morph.stats();
+
+        assert_eq!(
+            stats.total,
+            EditCounts {
+                inserts: 1,
+                deletes: 0,
+                substitutions: 2,
+            }
+        );
+        assert_eq!(stats.remaining.total(), morph.progress().remaining_edits);
+        assert_eq!(stats.remaining.substitutions, 1);
+    }
+
+    #[test]
+    fn fewest_substitutions_profile_deletes_and_inserts() {
+        let mut morph =
+            MorphingString::new("cat".to_string()).with_profile(Profile::FewestSubstitutions);
+        morph.set_target("cut".to_string());
+
+        assert_eq!(morph.stats().total.substitutions, 0);
+        assert_eq!(run(&mut morph), vec!["cat", "ct", "cut"]);
+    }
+
+    #[test]
+    fn pending_positions_follow_shifting_indexes() {
+        let mut morph = MorphingString::new("kitten".to_string());
+        morph.set_target("sitting".to_string());
+
+        assert_eq!(morph.pending_positions().collect::<Vec<_>>(), vec![0, 4]);
+        morph.advance();
+        assert_eq!(morph.pending_positions().collect::<Vec<_>>(), vec![4]);
+    }
+
+    #[test]
+    fn semantic_cleanup_replaces_whole_words() {
+        let mut morph = MorphingString::new("mouse".to_string()).with_semantic_cleanup();
+        morph.set_target("sofas".to_string());
+
+        // Without the cleanup, the lone 'o' would be kept, taking 4 edits.
+        assert_eq!(run(&mut morph).last().unwrap(), "sofas");
+        assert_eq!(morph.progress().total_edits, 5);
+    }
+
+    #[test]
+    fn highlights_last_and_next_changes() {
+        let mut morph = MorphingString::new("cat".to_string());
+        morph.set_target("chart".to_string());
+
+        // "cat" -> "chat" -> "chart"
+        morph.advance();
+
+        assert_eq!(morph.last_edits(), &[Edit::Insert { c: 'h', index: 1 }]);
+        assert_eq!(
+            morph.highlights(),
+            vec![None, Some(Highlight::Inserted), None, None]
+        );
+    }
+
+    /// Counts the allocations of every thread, to check code meant not to allocate.
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn short_morphs_do_not_allocate() {
+        let mut morph = MorphingString::new("12:59".to_string());
+        // The first plan allocates the buffers the later ones reuse.
+        morph.set_target("13:00".to_string());
+        while !morph.advance().is_complete() {}
+        let targets = ["12:59".to_string(), "13:00".to_string()];
+
+        let allocations = ALLOCATIONS.with(Cell::get);
+        for target in targets {
+            morph.set_target(target);
+            assert!(!morph.remaining_edits.spilled());
+            while !morph.advance().is_complete() {
+                assert!(!morph.last_step.spilled());
+            }
+        }
+
+        assert_eq!(ALLOCATIONS.with(Cell::get), allocations);
+        assert!(!morph.current_value.spilled());
+    }
+
+    #[test]
+    fn char_count_and_display_width_follow_edits() {
+        let mut morph = MorphingString::new("abc".to_string());
+        let snapshot = morph.snapshot();
+        morph.set_target("日本x".to_string());
+
+        loop {
+            assert_eq!(morph.char_count(), morph.value().chars().count());
+            assert_eq!(morph.display_width(), display_width(morph.value()));
+            if morph.advance().is_complete() {
+                break;
+            }
+        }
+        assert_eq!((morph.char_count(), morph.display_width()), (3, 5));
+
+        morph.restore(snapshot);
+        assert_eq!((morph.char_count(), morph.display_width()), (3, 3));
+    }
+
+    #[test]
+    fn random_access_into_the_value() {
+        for value in ["morphing", "mořphing"] {
+            let morph = MorphingString::new(value.to_string());
+
+            assert_eq!(morph.char_at(2), value.chars().nth(2));
+            assert_eq!(morph.char_at(8), None);
+            assert_eq!(
+                morph.slice(1..4),
+                value.chars().skip(1).take(3).collect::<String>()
+            );
+            assert_eq!(morph.slice(6..), "ng");
+            assert_eq!(morph.slice(..=1), "mo");
+            assert_eq!(morph.slice(5..100), "ing");
+            assert_eq!(morph.slice(9..20), "");
+            assert_eq!(morph.slice(..=usize::MAX), value);
+            assert_eq!(
+                morph.slice((Bound::Excluded(usize::MAX), Bound::Unbounded)),
+                ""
+            );
+        }
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derived_companion_morphs_string_fields() {
+        #[derive(Morphing)]
+        struct Status {
+            title: String,
+            #[allow(dead_code)]
+            count: u32,
+            detail: std::string::String,
+        }
+
+        let mut status = StatusMorphing::new(&Status {
+            title: "idle".to_string(),
+            count: 0,
+            detail: "".to_string(),
+        });
+        status.set_target(&Status {
+            title: "busy".to_string(),
+            count: 1,
+            detail: "working".to_string(),
+        });
+
+        assert_eq!(status.advance_all().total_edits, 4 + 7);
+        while !status.advance_all().is_complete() {}
+
+        assert_eq!(status.title.value(), "busy");
+        assert_eq!(status.detail.value(), "working");
+    }
 }
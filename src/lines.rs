@@ -0,0 +1,212 @@
+use std::{
+    collections::HashMap,
+    io::{self, Read},
+};
+
+use crate::{MorphingString, Progress, edit::Edit, levenshtein::compute_edit_sequence};
+
+/// Multi-line text morphed line by line. Lines are diffed as a whole first, so unchanged lines
+/// stay put, added lines are typed in, removed lines are erased and only the changed lines morph
+/// char by char, e.g. for animating changes of a config file.
+pub struct MorphingText {
+    lines: Vec<Line>,
+}
+
+struct Line {
+    morph: MorphingString,
+    // Whether the line is being erased, to be dropped once empty.
+    removed: bool,
+}
+
+impl Line {
+    fn new(value: String) -> Self {
+        Self {
+            morph: MorphingString::new(value),
+            removed: false,
+        }
+    }
+}
+
+impl MorphingText {
+    pub fn new(value: &str) -> Self {
+        Self {
+            lines: value
+                .lines()
+                .map(|line| Line::new(line.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Read the text to morph from `a` and the target from `b`.
+    pub fn from_readers(mut a: impl Read, mut b: impl Read) -> io::Result<Self> {
+        let mut value = String::new();
+        a.read_to_string(&mut value)?;
+        let mut target = String::new();
+        b.read_to_string(&mut target)?;
+
+        let mut text = Self::new(&value);
+        text.set_target(&target);
+        Ok(text)
+    }
+
+    /// Morph towards the lines of `target`, starting from the current, possibly intermediate,
+    /// lines. Lines still being erased are dropped right away.
+    pub fn set_target(&mut self, target: &str) {
+        self.lines.retain(|line| !line.removed);
+        let current: Vec<&str> = self.lines.iter().map(|line| line.morph.value()).collect();
+        let targets: Vec<&str> = target.lines().collect();
+
+        // Stand every distinct line in for a private use char, and diff those.
+        let mut ids: HashMap<&str, char> = HashMap::new();
+        let mut lines: HashMap<char, &str> = HashMap::new();
+        for line in current.iter().chain(&targets) {
+            if !ids.contains_key(line) {
+                let Some(id) = private_use(ids.len()) else {
+                    break;
+                };
+                ids.insert(line, id);
+                lines.insert(id, line);
+            }
+        }
+        let stand_ins = |lines: &[&str]| -> Option<String> {
+            lines.iter().map(|line| ids.get(line).copied()).collect()
+        };
+        let edits: Vec<(Edit, &str)> = match (stand_ins(&current), stand_ins(&targets)) {
+            (Some(current), Some(targets)) => compute_edit_sequence(&current, &targets)
+                .into_iter()
+                .map(|edit| match edit {
+                    Edit::Insert { c, .. } | Edit::Substitute { c, .. } => (edit, lines[&c]),
+                    Edit::Delete { .. } => (edit, ""),
+                })
+                .collect(),
+            // Too many distinct lines to tell apart, pair them up by position instead.
+            _ => (0..current.len().max(targets.len()))
+                .map(|index| match targets.get(index) {
+                    Some(target) if index < current.len() => {
+                        (Edit::Substitute { c: ' ', index }, *target)
+                    }
+                    Some(target) => (Edit::Insert { c: ' ', index }, *target),
+                    None => (
+                        Edit::Delete {
+                            index: targets.len(),
+                        },
+                        "",
+                    ),
+                })
+                .collect(),
+        };
+        let edits: Vec<(Edit, String)> = edits
+            .into_iter()
+            .map(|(edit, target)| (edit, target.to_string()))
+            .collect();
+
+        // The indexes into `self.lines` of the lines that aren't being removed, which is what the
+        // indexes of the edits refer to.
+        let mut live: Vec<usize> = (0..self.lines.len()).collect();
+        for (edit, target) in edits {
+            match edit {
+                Edit::Insert { index, .. } => {
+                    let position = match live.get(index) {
+                        Some(&position) => position,
+                        None => live.last().map_or(0, |last| last + 1),
+                    };
+                    for line in live.iter_mut().filter(|line| **line >= position) {
+                        *line += 1;
+                    }
+                    live.insert(index, position);
+                    self.lines.insert(position, Line::new(String::new()));
+                    self.lines[position].morph.set_target(target);
+                }
+                Edit::Delete { index } => {
+                    let position = live.remove(index);
+                    self.lines[position].removed = true;
+                    self.lines[position].morph.set_target(target);
+                }
+                Edit::Substitute { index, .. } => {
+                    self.lines[live[index]].morph.set_target(target);
+                }
+            }
+        }
+    }
+
+    /// Advance every line by one step.
+    pub fn advance(&mut self) -> Progress {
+        for line in &mut self.lines {
+            line.morph.advance();
+        }
+        let progress = self.progress();
+        self.lines
+            .retain(|line| !(line.removed && line.morph.progress().is_complete()));
+        progress
+    }
+
+    /// Combined progress of all the lines.
+    pub fn progress(&self) -> Progress {
+        self.lines.iter().map(|line| line.morph.progress()).fold(
+            Progress {
+                total_edits: 0,
+                remaining_edits: 0,
+            },
+            |sum, progress| Progress {
+                total_edits: sum.total_edits + progress.total_edits,
+                remaining_edits: sum.remaining_edits + progress.remaining_edits,
+            },
+        )
+    }
+
+    /// The current, possibly intermediate, lines.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().map(|line| line.morph.value())
+    }
+}
+
+/// The `id`-th char of the supplementary private use areas, or None if there aren't that many.
+pub(crate) fn private_use(id: usize) -> Option<char> {
+    // Each of the areas ends with two noncharacters.
+    const AREA_LEN: u32 = 0xfffe;
+    let id = u32::try_from(id).ok()?;
+    match id {
+        id if id < AREA_LEN => char::from_u32(0xf0000 + id),
+        id if id < 2 * AREA_LEN => char::from_u32(0x100000 + id - AREA_LEN),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(text: &mut MorphingText) -> Vec<Vec<String>> {
+        let mut frames = Vec::new();
+        while !text.advance().is_complete() {
+            frames.push(text.lines().map(str::to_string).collect());
+        }
+        frames.push(text.lines().map(str::to_string).collect());
+        frames
+    }
+
+    #[test]
+    fn only_changed_lines_morph() {
+        let mut text = MorphingText::from_readers(
+            "a = 1\nb = 2\nc = 3\n".as_bytes(),
+            "a = 1\nc = 4\n".as_bytes(),
+        )
+        .unwrap();
+
+        let frames = run(&mut text);
+
+        assert_eq!(frames[0], ["a = 1", " = 2", "c = 4"]);
+        assert_eq!(frames.last().unwrap(), &["a = 1", "c = 4"]);
+        assert!(frames.iter().all(|lines| lines[0] == "a = 1"));
+    }
+
+    #[test]
+    fn retargeting_midway_drops_erased_lines() {
+        let mut text = MorphingText::new("one\ntwo");
+        text.set_target("two");
+        text.advance();
+        text.set_target("two\nthree");
+
+        assert_eq!(run(&mut text).last().unwrap(), &["two", "three"]);
+    }
+}
@@ -0,0 +1,154 @@
+//! Conversion of [`EditScript`]s into LSP-style text edits.
+
+use crate::{
+    edit::Edit,
+    position::{Columns, LineColumn},
+    script::EditScript,
+};
+
+/// A zero-based position with `character` counted in UTF-16 code units, as in the LSP spec. Lines
+/// end with `\n`, `\r\n` or a lone `\r`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// Replacement of the text in `range` with `new_text`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextEdit {
+    pub range: Range,
+    pub new_text: String,
+}
+
+impl Position {
+    fn at(string: &str, byte_offset: usize) -> Self {
+        let LineColumn { line, column } = LineColumn::counting(Columns::Utf16, string, byte_offset);
+
+        Self {
+            line: line as u32,
+            character: column as u32,
+        }
+    }
+}
+
+impl EditScript {
+    /// Convert the script into LSP text edits against the document that starts out as `start`.
+    ///
+    /// Every edit's range refers to the document with all the previous edits already applied, so
+    /// the edits are meant to be sent one by one (e.g. as successive content changes) rather than
+    /// as a single batch.
+    pub fn to_lsp_text_edits(&self, start: &str) -> Vec<TextEdit> {
+        self.positions(start, |string, offset| {
+            let start = Position::at(string, offset);
+            let end = string[offset..]
+                .chars()
+                .next()
+                .map(|c| Position::at(string, offset + c.len_utf8()));
+            (start, end)
+        })
+        .map(|(edit, _, (start, end))| {
+            let (end, new_text) = match edit {
+                Edit::Insert { c, .. } => (start, c.to_string()),
+                Edit::Delete { .. } => (end.expect("deleted char exists"), String::new()),
+                Edit::Substitute { c, .. } => {
+                    (end.expect("substituted char exists"), c.to_string())
+                }
+            };
+
+            TextEdit {
+                range: Range { start, end },
+                new_text,
+            }
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Edit::*;
+
+    fn position(line: u32, character: u32) -> Position {
+        Position { line, character }
+    }
+
+    #[test]
+    fn ranges_count_utf16_code_units() {
+        let script: EditScript = [
+            Substitute { c: 'x', index: 1 },
+            Delete { index: 3 },
+            Insert {
+                c: '🦀', index: 3
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        let edits = script.to_lsp_text_edits("🦀a\nb");
+
+        assert_eq!(
+            edits,
+            vec![
+                TextEdit {
+                    range: Range {
+                        start: position(0, 2),
+                        end: position(0, 3),
+                    },
+                    new_text: "x".to_string(),
+                },
+                TextEdit {
+                    range: Range {
+                        start: position(1, 0),
+                        end: position(1, 1),
+                    },
+                    new_text: String::new(),
+                },
+                TextEdit {
+                    range: Range {
+                        start: position(1, 0),
+                        end: position(1, 0),
+                    },
+                    new_text: "🦀".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn deleted_newline_spans_into_next_line() {
+        let script: EditScript = [Delete { index: 1 }].into_iter().collect();
+
+        let edits = script.to_lsp_text_edits("a\nb");
+
+        assert_eq!(
+            edits[0].range,
+            Range {
+                start: position(0, 1),
+                end: position(1, 0),
+            }
+        );
+    }
+
+    #[test]
+    fn carriage_returns_end_lines() {
+        let script: EditScript = [Substitute { c: 'x', index: 5 }].into_iter().collect();
+
+        let edits = script.to_lsp_text_edits("a\r\nb\rc");
+
+        assert_eq!(
+            edits[0].range,
+            Range {
+                start: position(2, 0),
+                end: position(2, 1),
+            }
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use unicode_width::UnicodeWidthChar;
+
+use crate::edit::Edit;
+
+/// The char count and display width of a string, kept up to date as edits apply to it.
+///
+/// The width sums the widths of the chars, which lets edits update it one char at a time. That's
+/// an approximation for sequences the terminal joins into a single glyph, like emoji with zero
+/// width joiners, which come out wider than with [`UnicodeWidthStr`](unicode_width::UnicodeWidthStr).
+/// [`display_width`] measures whole strings the same way so the two always agree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Measure {
+    pub chars: usize,
+    pub width: usize,
+}
+
+impl Measure {
+    pub fn of(string: &str) -> Self {
+        let mut measure = Self::default();
+        for c in string.chars() {
+            measure.add(c);
+        }
+        measure
+    }
+
+    /// Account for `edit` having been applied, replacing or deleting `removed`.
+    pub fn update(&mut self, edit: &Edit, removed: Option<char>) {
+        if let Some(c) = removed {
+            self.chars -= 1;
+            self.width -= width(c);
+        }
+        if let Edit::Insert { c, .. } | Edit::Substitute { c, .. } = *edit {
+            self.add(c);
+        }
+    }
+
+    /// The byte offset of the `index`-th char of `string`, which this measures, or its length past
+    /// the end. Constant time for ASCII, otherwise walks from the nearer end of `string`.
+    pub fn byte_offset(&self, string: &str, index: usize) -> usize {
+        if self.chars == string.len() {
+            // ASCII, every char is a byte.
+            return index.min(string.len());
+        }
+        if index >= self.chars {
+            return string.len();
+        }
+        if index <= self.chars / 2 {
+            return string
+                .char_indices()
+                .nth(index)
+                .map_or(string.len(), |(offset, _)| offset);
+        }
+        string
+            .char_indices()
+            .nth_back(self.chars - 1 - index)
+            .map_or(string.len(), |(offset, _)| offset)
+    }
+
+    fn add(&mut self, c: char) {
+        self.chars += 1;
+        self.width += width(c);
+    }
+}
+
+/// The display width of `string` as [`Measure`] counts it.
+pub(crate) fn display_width(string: &str) -> usize {
+    string.chars().map(width).sum()
+}
+
+fn width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_offsets_from_either_end() {
+        for string in ["abcd", "čau, světe", "🦀x🦀"] {
+            let measure = Measure::of(string);
+            for index in 0..=measure.chars + 1 {
+                let expected = string
+                    .char_indices()
+                    .nth(index)
+                    .map_or(string.len(), |(offset, _)| offset);
+                assert_eq!(
+                    measure.byte_offset(string, index),
+                    expected,
+                    "{string:?} {index}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Similarity metrics between strings, all in the range from 0 (nothing in common) to 1 (equal).
+//! Like the edit distance, they compare chars.
+
+use std::collections::HashMap;
+
+use crate::levenshtein::distance;
+
+/// The [`distance`] relative to the length of the longer string, turned into a similarity.
+pub fn normalized_levenshtein(a: &str, b: &str) -> f64 {
+    let len = a.chars().count().max(b.chars().count());
+    if len == 0 {
+        return 1.0;
+    }
+    1.0 - distance(a, b) as f64 / len as f64
+}
+
+/// The Jaro similarity, based on the number of matching chars close to each other and the number
+/// of transpositions among them.
+pub fn jaro(a: &str, b: &str) -> f64 {
+    let (a, b) = (chars(a), chars(b));
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut b_matched = vec![false; b.len()];
+    let mut a_matches = Vec::new();
+    for (i, &c) in a.iter().enumerate() {
+        let candidates = i.saturating_sub(window)..(i + window + 1).min(b.len());
+        if let Some(j) = candidates.into_iter().find(|&j| !b_matched[j] && b[j] == c) {
+            b_matched[j] = true;
+            a_matches.push(c);
+        }
+    }
+
+    if a_matches.is_empty() {
+        return 0.0;
+    }
+    let b_matches = b
+        .iter()
+        .zip(&b_matched)
+        .filter_map(|(c, &matched)| matched.then_some(c));
+    let transpositions = a_matches
+        .iter()
+        .zip(b_matches)
+        .filter(|(a, b)| a != b)
+        .count()
+        / 2;
+
+    let matches = a_matches.len() as f64;
+    (matches / a.len() as f64
+        + matches / b.len() as f64
+        + (matches - transpositions as f64) / matches)
+        / 3.0
+}
+
+/// The [`jaro`] similarity boosted for strings sharing a prefix of up to 4 chars.
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let jaro = jaro(a, b);
+    let prefix = a
+        .chars()
+        .zip(b.chars())
+        .take(4)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// The cosine similarity of the counts of `n` chars long substrings. Strings shorter than `n`
+/// count as a single substring.
+pub fn ngram_cosine(a: &str, b: &str, n: usize) -> f64 {
+    let (a, b) = (chars(a), chars(b));
+    let (a, b) = (ngrams(&a, n), ngrams(&b, n));
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    let dot: usize = a
+        .iter()
+        .filter_map(|(gram, count)| b.get(gram).map(|other| count * other))
+        .sum();
+    let norm = |grams: &HashMap<&[char], usize>| {
+        grams
+            .values()
+            .map(|count| (count * count) as f64)
+            .sum::<f64>()
+            .sqrt()
+    };
+    let norms = norm(&a) * norm(&b);
+
+    if norms == 0.0 {
+        0.0
+    } else {
+        dot as f64 / norms
+    }
+}
+
+fn chars(string: &str) -> Vec<char> {
+    string.chars().collect()
+}
+
+fn ngrams(chars: &[char], n: usize) -> HashMap<&[char], usize> {
+    let mut grams = HashMap::new();
+    if chars.is_empty() {
+        return grams;
+    }
+    for gram in chars.windows(n.clamp(1, chars.len())) {
+        *grams.entry(gram).or_insert(0) += 1;
+    }
+    grams
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-3, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn jaro_winkler_matches_reference_values() {
+        assert_close(jaro("martha", "marhta"), 0.944);
+        assert_close(jaro_winkler("martha", "marhta"), 0.961);
+        assert_close(jaro_winkler("dixon", "dicksonx"), 0.813);
+        assert_close(jaro_winkler("abc", "xyz"), 0.0);
+        assert_close(jaro_winkler("", ""), 1.0);
+    }
+
+    #[test]
+    fn normalized_levenshtein_relates_to_length() {
+        assert_close(normalized_levenshtein("kitten", "sitting"), 1.0 - 3.0 / 7.0);
+        assert_close(normalized_levenshtein("", ""), 1.0);
+    }
+
+    #[test]
+    fn ngram_cosine_compares_substrings() {
+        assert_close(ngram_cosine("night", "nacht", 2), 0.25);
+        assert_close(ngram_cosine("abab", "abab", 2), 1.0);
+        assert_close(ngram_cosine("a", "", 2), 0.0);
+    }
+}
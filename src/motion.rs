@@ -0,0 +1,14 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static REDUCED_MOTION: AtomicBool = AtomicBool::new(false);
+
+/// Make every morph of the process jump straight to its target in a single step, e.g. to honour
+/// an accessibility setting or to keep integration tests quick and deterministic.
+pub fn set_reduced_motion(reduced: bool) {
+    REDUCED_MOTION.store(reduced, Ordering::Relaxed);
+}
+
+/// Whether [`set_reduced_motion`] turned animations off for the whole process.
+pub fn reduced_motion() -> bool {
+    REDUCED_MOTION.load(Ordering::Relaxed)
+}
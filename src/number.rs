@@ -0,0 +1,145 @@
+use crate::{Anchor, MorphingString, Progress};
+
+/// How a [`MorphingNumber`] writes numbers, e.g. `1,234.5` or `1.234,5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    grouping: Option<char>,
+    decimal_mark: char,
+    decimals: usize,
+}
+
+impl Default for NumberFormat {
+    /// Whole numbers with digits grouped by commas, e.g. `1,234,567`.
+    fn default() -> Self {
+        Self {
+            grouping: Some(','),
+            decimal_mark: '.',
+            decimals: 0,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Separate groups of three digits of the whole part with `separator`.
+    pub fn with_grouping(mut self, separator: char) -> Self {
+        self.grouping = Some(separator);
+        self
+    }
+
+    pub fn without_grouping(mut self) -> Self {
+        self.grouping = None;
+        self
+    }
+
+    pub fn with_decimal_mark(mut self, mark: char) -> Self {
+        self.decimal_mark = mark;
+        self
+    }
+
+    /// Round numbers to `decimals` decimal places.
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    pub fn format(&self, number: f64) -> String {
+        let digits = format!("{:.*}", self.decimals, number.abs());
+        let (whole, fraction) = match digits.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (digits.as_str(), None),
+        };
+
+        let mut formatted = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+        if number.is_sign_negative() && digits.bytes().any(|digit| digit != b'0' && digit != b'.') {
+            formatted.push('-');
+        }
+        for (i, digit) in whole.chars().enumerate() {
+            if let Some(separator) = self.grouping
+                && i > 0
+                && (whole.len() - i) % 3 == 0
+            {
+                formatted.push(separator);
+            }
+            formatted.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            formatted.push(self.decimal_mark);
+            formatted.push_str(fraction);
+        }
+        formatted
+    }
+}
+
+/// A number morphing digit by digit between values written in a [`NumberFormat`].
+///
+/// The digits are aligned from the right, so as long as the number of digits stays the same the
+/// separators stay put and only the digits change, e.g. `1.234.567` into `1.250.000`.
+pub struct MorphingNumber {
+    morph: MorphingString,
+    format: NumberFormat,
+}
+
+impl MorphingNumber {
+    pub fn new(number: f64, format: NumberFormat) -> Self {
+        Self {
+            morph: MorphingString::new(format.format(number)).with_anchor(Anchor::Right),
+            format,
+        }
+    }
+
+    pub fn set_target(&mut self, number: f64) {
+        self.morph.set_target(self.format.format(number));
+    }
+
+    pub fn advance(&mut self) -> Progress {
+        self.morph.advance()
+    }
+
+    pub fn progress(&self) -> Progress {
+        self.morph.progress()
+    }
+
+    /// The current, possibly intermediate, formatted number.
+    pub fn value(&self) -> &str {
+        self.morph.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_are_grouped_with_the_configured_marks() {
+        let format = NumberFormat::default()
+            .with_grouping('.')
+            .with_decimal_mark(',')
+            .with_decimals(2);
+
+        assert_eq!(format.format(1234567.891), "1.234.567,89");
+        assert_eq!(format.format(-12.0), "-12,00");
+        assert_eq!(format.format(-0.001), "0,00");
+        assert_eq!(NumberFormat::default().format(999.0), "999");
+        assert_eq!(
+            NumberFormat::default().without_grouping().format(1e6),
+            "1000000"
+        );
+    }
+
+    #[test]
+    fn separators_stay_put_while_digits_change() {
+        let mut number = MorphingNumber::new(1234567.0, NumberFormat::default().with_grouping('.'));
+        number.set_target(1250000.0);
+
+        while !number.advance().is_complete() {
+            let separators: Vec<usize> = number
+                .value()
+                .char_indices()
+                .filter(|&(_, c)| c == '.')
+                .map(|(index, _)| index)
+                .collect();
+            assert_eq!(separators, [1, 5], "{}", number.value());
+        }
+        assert_eq!(number.value(), "1.250.000");
+    }
+}
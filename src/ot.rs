@@ -0,0 +1,75 @@
+//! Conversion of [`EditScript`]s into operational-transform style operations.
+
+use crate::{edit::Edit, script::EditScript};
+
+/// A component of an OT operation. Lengths are in chars. An operation is a list of components
+/// that together span the whole document it applies to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Op {
+    Retain(usize),
+    Insert(String),
+    Delete(usize),
+}
+
+impl EditScript {
+    /// Convert every edit into an OT operation over the document that starts out as `start`.
+    ///
+    /// Each operation spans the whole document as it is after all the previous operations, so they
+    /// can be broadcast one by one to animate the change on remote clients. Substitutions become an
+    /// insert followed by a delete. Zero-length retains are omitted.
+    pub fn to_ot_operations(&self, start: &str) -> Vec<Vec<Op>> {
+        let mut len = start.chars().count();
+
+        self.iter()
+            .map(|edit| {
+                let index = edit.index();
+                let (components, consumed) = match edit {
+                    Edit::Insert { c, .. } => (vec![Op::Insert(c.to_string())], 0),
+                    Edit::Delete { .. } => (vec![Op::Delete(1)], 1),
+                    Edit::Substitute { c, .. } => {
+                        (vec![Op::Insert(c.to_string()), Op::Delete(1)], 1)
+                    }
+                };
+
+                let mut operation = Vec::with_capacity(components.len() + 2);
+                if index > 0 {
+                    operation.push(Op::Retain(index));
+                }
+                operation.extend(components);
+                if len > index + consumed {
+                    operation.push(Op::Retain(len - index - consumed));
+                }
+
+                match edit {
+                    Edit::Insert { .. } => len += 1,
+                    Edit::Delete { .. } => len -= 1,
+                    Edit::Substitute { .. } => {}
+                }
+
+                operation
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff;
+    use Op::*;
+
+    #[test]
+    fn operations_span_the_evolving_document() {
+        let operations = diff("abcdef", "xazced").to_ot_operations("abcdef");
+
+        assert_eq!(
+            operations,
+            vec![
+                vec![Insert("x".to_string()), Retain(6)],
+                vec![Retain(2), Insert("z".to_string()), Delete(1), Retain(4)],
+                vec![Retain(4), Delete(1), Retain(2)],
+                vec![Retain(5), Insert("d".to_string()), Delete(1)],
+            ]
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use std::{
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+/// Paces frames at a steady rate, accounting for the time spent rendering between them, so
+/// animations neither drift like with a plain sleep after every frame nor busy-wait.
+#[derive(Debug, Clone)]
+pub struct FramePacer {
+    interval: Duration,
+    next_frame: Instant,
+}
+
+impl FramePacer {
+    /// Pace `fps` frames per second, starting now.
+    pub fn new(fps: u32) -> Self {
+        Self::from_interval(Duration::from_secs(1) / fps.max(1))
+    }
+
+    /// Pace a frame every `interval`, starting now.
+    pub fn from_interval(interval: Duration) -> Self {
+        Self {
+            interval,
+            next_frame: Instant::now() + interval,
+        }
+    }
+
+    /// How long until the next frame is due, e.g. to wait for input events meanwhile.
+    pub fn until_next_frame(&self) -> Duration {
+        self.next_frame.saturating_duration_since(Instant::now())
+    }
+
+    /// Sleep until the next frame is due and schedule the one after it.
+    pub fn wait_for_next_frame(&mut self) {
+        sleep(self.until_next_frame());
+        self.schedule_next(Instant::now());
+    }
+
+    /// Change the interval, rescheduling the next frame to be due `interval` after the previous
+    /// one, e.g. to [`MorphingString::next_step_duration`](crate::MorphingString::next_step_duration).
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.next_frame = self.next_frame - self.interval + interval;
+        self.interval = interval;
+    }
+
+    /// Schedule the frame after the one due now. Frames missed by more than an interval, e.g.
+    /// when rendering took too long, are skipped rather than rendered in a burst.
+    fn schedule_next(&mut self, now: Instant) {
+        self.next_frame += self.interval;
+        if self.next_frame <= now {
+            self.next_frame = now + self.interval;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_keep_to_the_interval() {
+        let mut pacer = FramePacer::from_interval(Duration::from_millis(10));
+        let start = pacer.next_frame;
+
+        // Rendering took a few milliseconds, which the next frame doesn't wait for again.
+        pacer.schedule_next(start + Duration::from_millis(3));
+        assert_eq!(pacer.next_frame, start + Duration::from_millis(10));
+
+        // Frames missed by a long render are skipped.
+        pacer.schedule_next(start + Duration::from_millis(35));
+        assert_eq!(pacer.next_frame, start + Duration::from_millis(45));
+    }
+
+    #[test]
+    fn new_interval_applies_to_the_pending_frame() {
+        let mut pacer = FramePacer::from_interval(Duration::from_millis(10));
+        let start = pacer.next_frame;
+
+        pacer.set_interval(Duration::from_millis(30));
+        assert_eq!(pacer.next_frame, start + Duration::from_millis(20));
+        pacer.schedule_next(start + Duration::from_millis(21));
+        assert_eq!(pacer.next_frame, start + Duration::from_millis(50));
+    }
+
+    #[test]
+    fn fps_sets_the_interval() {
+        assert_eq!(FramePacer::new(50).interval, Duration::from_millis(20));
+        assert!(FramePacer::new(50).until_next_frame() <= Duration::from_millis(20));
+    }
+}
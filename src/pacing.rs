@@ -0,0 +1,237 @@
+use std::time::Duration;
+
+use crate::{MorphingString, edit::Edit};
+
+/// How the pace of a morph changes over its course.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Every frame stays for the same time.
+    #[default]
+    Linear,
+    /// Start slow and speed up.
+    EaseIn,
+    /// Start fast and slow down.
+    EaseOut,
+    /// Start and end slow.
+    EaseInOut,
+}
+
+impl Easing {
+    /// The part of the duration of a morph that has passed once `t` of its frames were shown.
+    fn apply(self, t: f64) -> f64 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// How many intervals an edit of every kind takes. See [`Pacing::with_weights`] and
+/// [`MorphingString::with_edit_weights`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditWeights {
+    pub insert: u32,
+    pub delete: u32,
+    pub substitute: u32,
+}
+
+impl Default for EditWeights {
+    /// Every edit takes an interval.
+    fn default() -> Self {
+        Self {
+            insert: 1,
+            delete: 1,
+            substitute: 1,
+        }
+    }
+}
+
+impl EditWeights {
+    /// The weight of a step applying `edits` together, that of its heaviest edit.
+    pub(crate) fn step(&self, edits: impl IntoIterator<Item = Edit>) -> u32 {
+        edits
+            .into_iter()
+            .map(|edit| match edit {
+                Edit::Insert { .. } => self.insert,
+                Edit::Delete { .. } => self.delete,
+                Edit::Substitute { .. } => self.substitute,
+            })
+            .max()
+            .unwrap_or(1)
+    }
+}
+
+/// How long every frame of a morph is shown for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pacing {
+    interval: Duration,
+    easing: Easing,
+    hold: Duration,
+    weights: EditWeights,
+    words_per_minute: Option<u32>,
+}
+
+impl Pacing {
+    /// Show every frame but the last one for `interval` on average.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            easing: Easing::default(),
+            hold: Duration::ZERO,
+            weights: EditWeights::default(),
+            words_per_minute: None,
+        }
+    }
+
+    /// Spread the same total duration over the frames according to `easing`.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Show the last frame for `hold`, e.g. to pause on the target before an animation loops.
+    pub fn with_hold(mut self, hold: Duration) -> Self {
+        self.hold = hold;
+        self
+    }
+
+    /// Show every frame for as many intervals as the edits producing the next one weigh, e.g. to
+    /// linger on insertions and rush through deletions. Easing applies on top. Weights of the morph
+    /// set with [`MorphingString::with_edit_weights`] multiply these.
+    pub fn with_weights(mut self, weights: EditWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Make a morph take as long as reading its target at `words_per_minute` rather than an
+    /// interval per edit, so short labels snap quickly while sentences morph slowly enough to be
+    /// read along.
+    pub fn with_reading_speed(mut self, words_per_minute: u32) -> Self {
+        self.words_per_minute = Some(words_per_minute.max(1));
+        self
+    }
+
+    /// Pair the current value of `morph` and every frame until the morph completes with how long
+    /// it's shown for, e.g. for encoders of animated images or frontends scheduling frames
+    /// themselves.
+    pub fn timed_frames(&self, morph: &mut MorphingString) -> Vec<(String, Duration)> {
+        let mut frames = vec![morph.value().to_string()];
+        // The total weight of the steps up to every frame.
+        let mut weights = vec![0];
+        while !morph.progress().is_complete() {
+            let morph_weight = morph.next_step_weight();
+            morph.advance();
+            frames.push(morph.value().to_string());
+            let weight = self.weights.step(morph.last_edits().iter().copied()) * morph_weight;
+            weights.push(weights[weights.len() - 1] + weight);
+        }
+
+        let transitions = frames.len() - 1;
+        let total_weight = weights[transitions];
+        let total = match self.words_per_minute {
+            Some(words_per_minute) => {
+                let words = frames[transitions].split_whitespace().count();
+                Duration::from_secs(60) * words as u32 / words_per_minute
+            }
+            None => self.interval * total_weight,
+        };
+        // When every frame is shown, relative to the start of the morph.
+        let shown_at = |frame: usize| {
+            let progress = weights[frame] as f64 / total_weight.max(1) as f64;
+            total.mul_f64(self.easing.apply(progress))
+        };
+
+        frames
+            .into_iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                let duration = if i == transitions {
+                    self.hold
+                } else {
+                    shown_at(i + 1) - shown_at(i)
+                };
+                (frame, duration)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn durations(pacing: Pacing) -> Vec<u64> {
+        let mut morph = MorphingString::new(String::new());
+        morph.set_target("abcd".to_string());
+        pacing
+            .timed_frames(&mut morph)
+            .into_iter()
+            .map(|(_, duration)| duration.as_millis() as u64)
+            .collect()
+    }
+
+    #[test]
+    fn linear_frames_share_the_interval() {
+        let pacing = Pacing::new(Duration::from_millis(100)).with_hold(Duration::from_secs(1));
+
+        assert_eq!(durations(pacing), vec![100, 100, 100, 100, 1000]);
+    }
+
+    #[test]
+    fn easing_keeps_the_total_duration() {
+        let pacing = Pacing::new(Duration::from_millis(100)).with_easing(Easing::EaseInOut);
+        let durations = durations(pacing);
+
+        assert_eq!(durations, vec![62, 137, 137, 62, 0]);
+        assert!(durations.iter().sum::<u64>().abs_diff(400) <= 2);
+    }
+
+    #[test]
+    fn weights_stretch_frames_by_edit_kind() {
+        let mut morph = MorphingString::new("ab".to_string());
+        morph.set_target("xbc".to_string());
+        let pacing = Pacing::new(Duration::from_millis(100)).with_weights(EditWeights {
+            insert: 2,
+            delete: 1,
+            substitute: 3,
+        });
+
+        let durations: Vec<u128> = pacing
+            .timed_frames(&mut morph)
+            .into_iter()
+            .map(|(_, duration)| duration.as_millis())
+            .collect();
+
+        assert_eq!(durations, vec![300, 200, 0]);
+    }
+
+    #[test]
+    fn reading_speed_scales_with_the_words_of_the_target() {
+        let total = |target: &str| -> Duration {
+            let mut morph = MorphingString::new(String::new());
+            morph.set_target(target.to_string());
+            let pacing = Pacing::new(Duration::from_millis(100)).with_reading_speed(240);
+            pacing
+                .timed_frames(&mut morph)
+                .into_iter()
+                .map(|(_, duration)| duration)
+                .sum()
+        };
+
+        assert_eq!(total("OK"), Duration::from_millis(250));
+        assert_eq!(total("a b c d"), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn finished_morph_is_a_single_frame() {
+        let mut morph = MorphingString::new("abc".to_string());
+        let pacing = Pacing::new(Duration::from_millis(100));
+
+        assert_eq!(
+            pacing.timed_frames(&mut morph),
+            vec![("abc".to_string(), Duration::ZERO)]
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    anchor::Anchor,
+    levenshtein::{Costs, compute_weighted_edit_sequence},
+    script::EditScript,
+    tie_break::TieBreak,
+};
+
+/// Plans the edits a [`MorphingString`](crate::MorphingString) applies to get from one value to
+/// another, e.g. to type the target out like a typewriter or reveal it in a domain-specific order.
+///
+/// The returned script must turn `from` into `to`.
+pub trait MorphPlanner {
+    fn plan(&self, from: &str, to: &str) -> EditScript;
+}
+
+impl<F: Fn(&str, &str) -> EditScript> MorphPlanner for F {
+    fn plan(&self, from: &str, to: &str) -> EditScript {
+        self(from, to)
+    }
+}
+
+/// The default planner, planning the cheapest edits by Levenshtein distance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LevenshteinPlanner {
+    costs: Costs,
+    anchor: Anchor,
+    tie_break: TieBreak,
+}
+
+impl LevenshteinPlanner {
+    pub fn with_costs(mut self, costs: Costs) -> Self {
+        self.costs = costs;
+        self
+    }
+
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+}
+
+impl MorphPlanner for LevenshteinPlanner {
+    fn plan(&self, from: &str, to: &str) -> EditScript {
+        compute_weighted_edit_sequence(from, to, &self.costs, self.anchor, self.tie_break).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit::Edit;
+
+    #[test]
+    fn closures_plan() {
+        // Delete everything, then type the target out.
+        let typewriter = |from: &str, to: &str| -> EditScript {
+            let deletes = from.chars().map(|_| Edit::Delete { index: 0 });
+            let inserts = to
+                .chars()
+                .enumerate()
+                .map(|(index, c)| Edit::Insert { c, index });
+            deletes.chain(inserts).collect()
+        };
+
+        assert_eq!(typewriter.plan("ab", "xyz").len(), 5);
+        assert_eq!(typewriter.plan("ab", "xyz").apply("ab"), "xyz");
+    }
+
+    #[test]
+    fn levenshtein_plans_the_cheapest_edits() {
+        let script = LevenshteinPlanner::default().plan("kitten", "sitting");
+
+        assert_eq!(script.len(), 3);
+        assert_eq!(script.apply("kitten"), "sitting");
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::VecDeque;
+
+use crate::{MorphingString, edit::Edit, levenshtein::Scratch};
+
+/// A cyclic sequence of targets with the edits between every consecutive pair computed up front,
+/// so retargeting a [`MorphingString`] to the next entry never has to stop and plan.
+///
+/// The edits are planned with the planner, costs, equivalences and cleanup of the morph the
+/// playlist is created for, so they match what [`MorphingString::set_target`] would plan.
+pub struct Playlist {
+    targets: Vec<String>,
+    // edits[i] turns targets[i] into targets[(i + 1) % targets.len()].
+    edits: Vec<VecDeque<Edit>>,
+    next: usize,
+}
+
+/// Edits planned ahead of time for morphing from `start` to `target`.
+pub(crate) struct Prepared {
+    pub start: String,
+    pub target: String,
+    pub edits: VecDeque<Edit>,
+}
+
+impl Playlist {
+    pub fn new(morph: &MorphingString, targets: Vec<String>) -> Self {
+        let mut scratch = Scratch::default();
+        let edits = (0..targets.len())
+            .map(|i| {
+                let next = &targets[(i + 1) % targets.len()];
+                morph.compute_edits(&mut scratch, &targets[i], next)
+            })
+            .collect();
+
+        Self {
+            targets,
+            edits,
+            next: 0,
+        }
+    }
+
+    /// Like [`Playlist::new`]. With the `rayon` feature enabled the entries are planned in
+    /// parallel.
+    pub fn new_parallel(morph: &MorphingString, targets: Vec<String>) -> Self {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            let len = targets.len();
+            let edits = (0..len)
+                .into_par_iter()
+                .map_init(Scratch::default, |scratch, i| {
+                    morph.compute_edits(scratch, &targets[i], &targets[(i + 1) % len])
+                })
+                .collect();
+
+            Self {
+                targets,
+                edits,
+                next: 0,
+            }
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        {
+            Self::new(morph, targets)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Set the next entry of the playlist as the target of `morph`, wrapping around at the end.
+    ///
+    /// Goes through [`MorphingString::set_target`], so history and the retarget policy apply as
+    /// usual. Precomputed edits are used when the morph starts from the previous entry. Otherwise
+    /// the edits are computed on the spot. Does nothing for an empty playlist.
+    pub fn retarget(&mut self, morph: &mut MorphingString) {
+        if self.targets.is_empty() {
+            return;
+        }
+
+        let previous = (self.next + self.targets.len() - 1) % self.targets.len();
+        let target = self.targets[self.next].clone();
+
+        morph.prepare(Prepared {
+            start: self.targets[previous].clone(),
+            target: target.clone(),
+            edits: self.edits[previous].clone(),
+        });
+        morph.set_target(target);
+
+        self.next = (self.next + 1) % self.targets.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Costs;
+
+    fn targets() -> Vec<String> {
+        ["kitten", "mittens", "sunday", "saturday", ""]
+            .iter()
+            .map(|target| target.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn parallel_planning_matches_sequential() {
+        let morph = MorphingString::new(String::new());
+
+        assert_eq!(
+            Playlist::new(&morph, targets()).edits,
+            Playlist::new_parallel(&morph, targets()).edits
+        );
+    }
+
+    #[test]
+    fn cycles_through_targets() {
+        let mut morph = MorphingString::new("unrelated".to_string());
+        let mut playlist = Playlist::new(&morph, targets());
+
+        for target in targets().iter().chain(targets().iter()) {
+            playlist.retarget(&mut morph);
+            while !morph.advance().is_complete() {}
+            assert_eq!(morph.value(), target);
+        }
+    }
+
+    #[test]
+    fn empty_playlist_does_nothing() {
+        let mut morph = MorphingString::new("abc".to_string());
+        let mut playlist = Playlist::new(&morph, vec![]);
+
+        playlist.retarget(&mut morph);
+
+        assert!(morph.progress().is_complete());
+        assert_eq!(morph.value(), "abc");
+    }
+
+    #[test]
+    fn retargeting_plans_like_set_target() {
+        let costs = Costs {
+            insert: 1,
+            delete: 1,
+            substitute: 3,
+            ..Costs::default()
+        };
+        let configure = || MorphingString::new("kitten".to_string()).with_costs(costs);
+        let mut morph = configure().with_history(8);
+        let mut expected = configure();
+        let mut playlist = Playlist::new(&morph, targets());
+
+        playlist.retarget(&mut morph);
+        for target in targets().into_iter().skip(1).take(3) {
+            playlist.retarget(&mut morph);
+            expected.set_target(target);
+            loop {
+                assert_eq!(
+                    morph.advance().is_complete(),
+                    expected.advance().is_complete()
+                );
+                assert_eq!(morph.value(), expected.value());
+                if expected.progress().is_complete() {
+                    break;
+                }
+            }
+        }
+        assert!(morph.back());
+        assert_eq!(morph.target, "sunday");
+    }
+}
@@ -0,0 +1,84 @@
+/// A zero-based position in a multi-line string. Columns count chars from the start of the line.
+///
+/// Lines end with `\n`, `\r\n` or a lone `\r`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The units columns are counted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Columns {
+    Chars,
+    /// UTF-16 code units, as in the LSP spec.
+    Utf16,
+}
+
+impl LineColumn {
+    /// The position of the char starting at `byte_offset` in `string`.
+    pub(crate) fn at(string: &str, byte_offset: usize) -> Self {
+        Self::counting(Columns::Chars, string, byte_offset)
+    }
+
+    /// Like [`LineColumn::at`] but with the column counted in `columns`.
+    pub(crate) fn counting(columns: Columns, string: &str, byte_offset: usize) -> Self {
+        let before = &string[..byte_offset];
+        let mut line = 0;
+        let mut line_start = 0;
+        for (index, c) in before.char_indices() {
+            let line_break = match c {
+                '\n' => true,
+                // The line of a `\r\n` ends after the `\n`.
+                '\r' => !string[index + 1..].starts_with('\n'),
+                _ => false,
+            };
+            if line_break {
+                line += 1;
+                line_start = index + 1;
+            }
+        }
+
+        let line_before = &before[line_start..];
+        Self {
+            line,
+            column: match columns {
+                Columns::Chars => line_before.chars().count(),
+                Columns::Utf16 => line_before.encode_utf16().count(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_in_multiline_string() {
+        let string = "ab\nčd\n";
+
+        assert_eq!(LineColumn::at(string, 0), LineColumn { line: 0, column: 0 });
+        assert_eq!(LineColumn::at(string, 2), LineColumn { line: 0, column: 2 });
+        assert_eq!(LineColumn::at(string, 3), LineColumn { line: 1, column: 0 });
+        assert_eq!(LineColumn::at(string, 5), LineColumn { line: 1, column: 1 });
+        assert_eq!(LineColumn::at(string, 7), LineColumn { line: 2, column: 0 });
+    }
+
+    #[test]
+    fn carriage_returns_end_lines() {
+        let string = "a\r\nb\rc";
+
+        assert_eq!(LineColumn::at(string, 1), LineColumn { line: 0, column: 1 });
+        assert_eq!(LineColumn::at(string, 2), LineColumn { line: 0, column: 2 });
+        assert_eq!(LineColumn::at(string, 3), LineColumn { line: 1, column: 0 });
+        assert_eq!(LineColumn::at(string, 5), LineColumn { line: 2, column: 0 });
+    }
+
+    #[test]
+    fn utf16_columns() {
+        let position = LineColumn::counting(Columns::Utf16, "x\n🦀č", 6);
+
+        assert_eq!(position, LineColumn { line: 1, column: 2 });
+    }
+}
@@ -0,0 +1,17 @@
+/// A curated combination of planning options. See [`MorphingString::with_profile`].
+///
+/// [`MorphingString::with_profile`]: crate::MorphingString::with_profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Profile {
+    /// The fewest edits possible, applied one at a time.
+    #[default]
+    Minimal,
+    /// The length only ever moves towards the length of the target, so surrounding content
+    /// doesn't jitter back and forth.
+    StableLength,
+    /// Deletions and insertions instead of substitutions, so every char of the value is either one
+    /// of the start or one of the target at its final place.
+    FewestSubstitutions,
+    /// Whole words change at once.
+    Blocky,
+}
@@ -0,0 +1,100 @@
+use crate::Progress;
+
+/// Renders progress as a textual bar like `[#####.....] 50%`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressBar {
+    width: usize,
+    filled: char,
+    empty: char,
+    percentage: bool,
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self {
+            width: 10,
+            filled: '#',
+            empty: '.',
+            percentage: true,
+        }
+    }
+}
+
+impl ProgressBar {
+    /// A bar `width` glyphs wide, not counting the brackets and the percentage.
+    pub fn new(width: usize) -> Self {
+        Self {
+            width,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_glyphs(mut self, filled: char, empty: char) -> Self {
+        self.filled = filled;
+        self.empty = empty;
+        self
+    }
+
+    pub fn without_percentage(mut self) -> Self {
+        self.percentage = false;
+        self
+    }
+
+    pub fn render(&self, progress: Progress) -> String {
+        self.render_ratio(
+            progress.total_edits - progress.remaining_edits,
+            progress.total_edits,
+        )
+    }
+
+    /// Render `done` out of `total` units of any work, e.g. rows of a huge input planned so far.
+    /// Nothing to do counts as done.
+    pub fn render_ratio(&self, done: usize, total: usize) -> String {
+        let (done, total) = if total == 0 {
+            (1, 1)
+        } else {
+            (done.min(total), total)
+        };
+        let filled = self.width * done / total;
+
+        let mut bar = String::with_capacity(self.width + 7);
+        bar.push('[');
+        bar.extend(std::iter::repeat_n(self.filled, filled));
+        bar.extend(std::iter::repeat_n(self.empty, self.width - filled));
+        bar.push(']');
+        if self.percentage {
+            bar.push_str(&format!(" {}%", 100 * done / total));
+        }
+        bar
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_progress() {
+        let progress = Progress {
+            total_edits: 12,
+            remaining_edits: 7,
+        };
+
+        assert_eq!(ProgressBar::default().render(progress), "[####......] 41%");
+    }
+
+    #[test]
+    fn renders_custom_glyphs_and_width() {
+        let bar = ProgressBar::new(4)
+            .with_glyphs('█', '░')
+            .without_percentage();
+
+        assert_eq!(bar.render_ratio(1, 2), "[██░░]");
+        assert_eq!(bar.render_ratio(5, 2), "[████]");
+    }
+
+    #[test]
+    fn nothing_to_do_is_complete() {
+        assert_eq!(ProgressBar::new(2).render_ratio(0, 0), "[##] 100%");
+    }
+}
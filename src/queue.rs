@@ -0,0 +1,117 @@
+use std::ops::Deref;
+
+use smallvec::SmallVec;
+
+use crate::edit::PackedEdit;
+
+/// The remaining edits of a morph. Short scripts, like those of most status lines, are kept inline
+/// so planning and advancing them doesn't allocate.
+///
+/// Advancing moves past the edits at the front instead of shifting the rest. The space they took is
+/// reclaimed once it makes up half of the queue, or all of it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EditQueue {
+    edits: SmallVec<[PackedEdit; 32]>,
+    // Number of edits at the front of `edits` already taken.
+    head: usize,
+}
+
+impl EditQueue {
+    /// Take the first `count` edits off the queue.
+    pub fn take_front(&mut self, count: usize) -> impl Iterator<Item = PackedEdit> + '_ {
+        let start = self.head;
+        self.head += count.min(self.len());
+        self.edits[start..self.head].iter().copied()
+    }
+
+    pub fn push_back(&mut self, edit: PackedEdit) {
+        self.reclaim();
+        self.edits.push(edit);
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        self.edits.truncate(self.head + len);
+    }
+
+    /// Replace all the edits with `edits`, reusing the space of the current ones.
+    pub fn replace(&mut self, edits: impl IntoIterator<Item = PackedEdit>) {
+        self.edits.clear();
+        self.head = 0;
+        self.edits.extend(edits);
+    }
+
+    fn reclaim(&mut self) {
+        if self.head == self.edits.len() {
+            self.edits.clear();
+            self.head = 0;
+        } else if 2 * self.head >= self.edits.len() {
+            self.edits.rotate_left(self.head);
+            self.edits.truncate(self.edits.len() - self.head);
+            self.head = 0;
+        }
+    }
+
+    #[cfg(test)]
+    pub fn spilled(&self) -> bool {
+        self.edits.spilled()
+    }
+}
+
+impl Deref for EditQueue {
+    type Target = [PackedEdit];
+
+    fn deref(&self) -> &[PackedEdit] {
+        &self.edits[self.head..]
+    }
+}
+
+impl Extend<PackedEdit> for EditQueue {
+    fn extend<I: IntoIterator<Item = PackedEdit>>(&mut self, edits: I) {
+        self.reclaim();
+        self.edits.extend(edits);
+    }
+}
+
+impl FromIterator<PackedEdit> for EditQueue {
+    fn from_iter<I: IntoIterator<Item = PackedEdit>>(edits: I) -> Self {
+        Self {
+            edits: edits.into_iter().collect(),
+            head: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit::{Edit, pack};
+
+    fn deletions(indexes: impl IntoIterator<Item = usize>) -> impl Iterator<Item = PackedEdit> {
+        indexes
+            .into_iter()
+            .map(|index| pack(Edit::Delete { index }))
+    }
+
+    #[test]
+    fn taken_edits_leave_the_front() {
+        let mut queue: EditQueue = deletions(0..4).collect();
+
+        assert!(queue.take_front(3).eq(deletions(0..3)));
+        queue.push_back(pack(Edit::Delete { index: 4 }));
+        assert!(queue.iter().copied().eq(deletions(3..5)));
+        assert_eq!(queue.head, 0);
+
+        assert!(queue.take_front(5).eq(deletions(3..5)));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn truncating_keeps_the_front() {
+        let mut queue: EditQueue = deletions(0..4).collect();
+        queue.take_front(1).for_each(drop);
+
+        queue.truncate(2);
+
+        assert!(queue.iter().copied().eq(deletions(1..3)));
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::mpsc::{Receiver, TryRecvError};
+
+use crate::{MorphingString, Progress};
+
+/// A [`MorphingString`] retargeting itself to every target received from an [`mpsc`] channel,
+/// according to its [`RetargetPolicy`](crate::RetargetPolicy). Decouples whatever produces the
+/// text from the loop rendering it.
+///
+/// [`mpsc`]: std::sync::mpsc
+pub struct ReceivingMorphingString {
+    morph: MorphingString,
+    targets: Receiver<String>,
+    disconnected: bool,
+}
+
+impl ReceivingMorphingString {
+    pub fn new(morph: MorphingString, targets: Receiver<String>) -> Self {
+        Self {
+            morph,
+            targets,
+            disconnected: false,
+        }
+    }
+
+    /// Set every target received since the last advance, then advance the morph.
+    pub fn advance(&mut self) -> Progress {
+        loop {
+            match self.targets.try_recv() {
+                Ok(target) => self.morph.set_target(target),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.disconnected = true;
+                    break;
+                }
+            }
+        }
+        self.morph.advance()
+    }
+
+    /// Whether all senders are gone so no more targets will arrive.
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected
+    }
+
+    pub fn morph(&self) -> &MorphingString {
+        &self.morph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn received_targets_are_morphed_to() {
+        let (sender, receiver) = mpsc::channel();
+        let mut receiving =
+            ReceivingMorphingString::new(MorphingString::new("ab".to_string()), receiver);
+
+        assert!(receiving.advance().is_complete());
+        sender.send("b".to_string()).unwrap();
+        sender.send("bc".to_string()).unwrap();
+        drop(sender);
+
+        // The latest target replaces the earlier one.
+        assert_eq!(receiving.advance().total_edits, 2);
+        assert!(receiving.advance().is_complete());
+        assert_eq!(receiving.morph().value(), "bc");
+        assert!(receiving.is_disconnected());
+    }
+}
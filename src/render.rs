@@ -0,0 +1,1044 @@
+//! Writing frames of a morph to any [`io::Write`](std::io::Write).
+
+use std::{
+    borrow::Cow,
+    io::{self, IsTerminal, Write},
+    time::Duration,
+};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    FramePacer, Highlight, MorphStyle, MorphingString,
+    edit::Edit,
+    style::{Fade, runs},
+};
+
+/// How [`write_frames`] and [`write_frame`] output frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteOptions {
+    in_place: bool,
+    ansi: bool,
+    interval: Duration,
+    width: Option<usize>,
+    ellipsis: String,
+    mask: Option<char>,
+    edit_mask: char,
+    tab_width: usize,
+    trailing_whitespace: TrailingWhitespace,
+}
+
+/// What [`write_frame`] remembers of the frames written before, to overwrite and pad the next
+/// one. Keep one per output and start afresh with the default for a new one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WriteState {
+    // The width of the widest frame prepared so far, which padded frames are padded to.
+    widest: usize,
+    // The width of the last frame written in place, which the next one overwrites without ANSI.
+    written: usize,
+}
+
+/// What happens to whitespace at the end of frames. See [`WriteOptions::with_trailing_whitespace`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingWhitespace {
+    /// Write frames as they are.
+    #[default]
+    Preserve,
+    /// Drop whitespace at the end of frames.
+    Strip,
+    /// Pad frames with spaces to the width of the widest frame written so far, so shorter frames
+    /// overwrite whatever longer ones left behind even without clearing the line.
+    Pad,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            in_place: true,
+            ansi: true,
+            interval: Duration::ZERO,
+            width: None,
+            ellipsis: String::new(),
+            mask: None,
+            edit_mask: '◦',
+            tab_width: 8,
+            trailing_whitespace: TrailingWhitespace::default(),
+        }
+    }
+}
+
+impl WriteOptions {
+    /// Update frames in place when `out` is a terminal and write a line per frame otherwise, e.g.
+    /// when piped into a file.
+    ///
+    /// On Windows the ANSI escape codes used to update frames in place are only enabled, through
+    /// crossterm, with the `crossterm` feature. Without it, or where enabling them fails, frames
+    /// are overwritten with spaces like with [`WriteOptions::without_ansi`]. Either way carriage
+    /// returns are dropped from frames, so CRLF line breaks are written as plain newlines.
+    pub fn detect(out: &impl IsTerminal) -> Self {
+        let in_place = out.is_terminal();
+        Self {
+            in_place,
+            ansi: !in_place || ansi_support(),
+            ..Self::default()
+        }
+    }
+
+    /// Write every frame on a new line instead of overwriting the previous one.
+    pub fn without_in_place(mut self) -> Self {
+        self.in_place = false;
+        self
+    }
+
+    /// Overwrite frames updated in place with spaces instead of clearing the line with ANSI escape
+    /// codes, for consoles that don't understand them, like older Windows consoles.
+    pub fn without_ansi(mut self) -> Self {
+        self.ansi = false;
+        self
+    }
+
+    /// Write a frame every `interval`, accounting for the time spent writing them.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Truncate frames to `width` terminal columns so they never wrap. Wrapped frames can't be
+    /// updated in place as only the last row gets cleared.
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// End truncated frames with `ellipsis`, e.g. `…`. It's left out when it doesn't fit the width
+    /// itself.
+    pub fn with_ellipsis(mut self, ellipsis: impl Into<String>) -> Self {
+        self.ellipsis = ellipsis.into();
+        self
+    }
+
+    /// Expand tabs to spaces up to the next multiple of `width` columns, 8 by default, so they're
+    /// measured and cleared like the terminal shows them.
+    pub fn with_tab_width(mut self, width: usize) -> Self {
+        self.tab_width = width.max(1);
+        self
+    }
+
+    pub fn with_trailing_whitespace(mut self, policy: TrailingWhitespace) -> Self {
+        self.trailing_whitespace = policy;
+        self
+    }
+
+    /// Write every char but line breaks as `mask`, e.g. `•`, so secrets and redacted fields
+    /// morph without showing their content. So substitutions still show, [`write_frames`] and the
+    /// renderers write the chars the last step inserted or substituted as `◦` instead. See
+    /// [`WriteOptions::with_edit_mask`].
+    pub fn with_mask(mut self, mask: char) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Write the chars the last step inserted or substituted as `mask` when masking frames.
+    pub fn with_edit_mask(mut self, mask: char) -> Self {
+        self.edit_mask = mask;
+        self
+    }
+}
+
+impl WriteOptions {
+    /// `frame` masked, with tabs expanded, trailing whitespace handled and truncated as it's
+    /// written after the frames `state` remembers. `highlights` are those of the chars of `frame`,
+    /// if known, and mark the edited ones when masking.
+    fn prepare<'a>(
+        &self,
+        frame: &'a str,
+        highlights: &[Option<Highlight>],
+        state: &mut WriteState,
+    ) -> Cow<'a, str> {
+        // A carriage return, e.g. of a CRLF line break, would return the cursor mid-frame.
+        let frame = match self.mask {
+            Some(mask) => Cow::Owned(
+                self.truncate(&self.mask(frame, highlights, mask))
+                    .into_owned(),
+            ),
+            None if frame.contains('\r') => {
+                Cow::Owned(self.shape(&frame.replace('\r', "")).into_owned())
+            }
+            None => self.shape(frame),
+        };
+        match self.trailing_whitespace {
+            TrailingWhitespace::Preserve => frame,
+            TrailingWhitespace::Strip => match frame {
+                Cow::Borrowed(frame) => Cow::Borrowed(frame.trim_end()),
+                Cow::Owned(frame) => Cow::Owned(frame.trim_end().to_string()),
+            },
+            TrailingWhitespace::Pad => {
+                let width = frame.width();
+                let widest = state.widest.max(width);
+                state.widest = widest;
+                if width == widest {
+                    return frame;
+                }
+                let mut padded = frame.into_owned();
+                padded.extend(std::iter::repeat_n(' ', widest - width));
+                Cow::Owned(padded)
+            }
+        }
+    }
+
+    /// `frame` with tabs expanded and truncated.
+    fn shape<'a>(&self, frame: &'a str) -> Cow<'a, str> {
+        match self.expand_tabs(frame) {
+            Cow::Borrowed(frame) => self.truncate(frame),
+            Cow::Owned(expanded) => Cow::Owned(self.truncate(&expanded).into_owned()),
+        }
+    }
+
+    /// Every char of `frame` but line breaks as `mask`, or as the edit mask when `highlights` mark
+    /// it inserted or substituted. Carriage returns are dropped.
+    fn mask(&self, frame: &str, highlights: &[Option<Highlight>], mask: char) -> String {
+        frame
+            .chars()
+            .enumerate()
+            .filter_map(|(index, c)| match c {
+                '\r' => None,
+                '\n' => Some(c),
+                _ => match highlights.get(index) {
+                    Some(Some(Highlight::Inserted | Highlight::Substituted)) => {
+                        Some(self.edit_mask)
+                    }
+                    _ => Some(mask),
+                },
+            })
+            .collect()
+    }
+
+    /// Replace every tab in `frame` with spaces up to the next tab stop.
+    fn expand_tabs<'a>(&self, frame: &'a str) -> Cow<'a, str> {
+        if !frame.contains('\t') {
+            return Cow::Borrowed(frame);
+        }
+
+        let mut expanded = String::with_capacity(frame.len());
+        let mut column = 0;
+        for c in frame.chars() {
+            match c {
+                '\t' => {
+                    let spaces = self.tab_width - column % self.tab_width;
+                    expanded.extend(std::iter::repeat_n(' ', spaces));
+                    column += spaces;
+                }
+                '\n' => {
+                    expanded.push(c);
+                    column = 0;
+                }
+                c => {
+                    expanded.push(c);
+                    column += c.width().unwrap_or(0);
+                }
+            }
+        }
+        Cow::Owned(expanded)
+    }
+
+    /// Cut `frame` to the width, measured in terminal columns, ending it with the ellipsis when
+    /// anything was cut.
+    fn truncate<'a>(&self, frame: &'a str) -> Cow<'a, str> {
+        let Some(width) = self.width else {
+            return Cow::Borrowed(frame);
+        };
+        if frame.width() <= width {
+            return Cow::Borrowed(frame);
+        }
+
+        let ellipsis = match self.ellipsis.width() {
+            ellipsis_width if ellipsis_width <= width => self.ellipsis.as_str(),
+            _ => "",
+        };
+        let budget = width - ellipsis.width();
+
+        let mut used = 0;
+        let end = frame
+            .char_indices()
+            .find(|&(_, c)| {
+                used += c.width().unwrap_or(0);
+                used > budget
+            })
+            .map_or(frame.len(), |(offset, _)| offset);
+
+        Cow::Owned(format!("{}{ellipsis}", &frame[..end]))
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl WriteOptions {
+    /// Like [`WriteOptions::detect`], additionally truncating frames to the width of the terminal.
+    pub fn for_terminal(out: &impl IsTerminal) -> Self {
+        let options = Self::detect(out);
+        match crossterm::terminal::size() {
+            Ok((columns, _)) if options.in_place => options.with_width(columns.into()),
+            _ => options,
+        }
+    }
+
+    /// Follow resizes of the terminal, clearing whatever the terminal reflowed `frame`, the last
+    /// written one, into and writing it again truncated to the new width. Other events, and all
+    /// events when not writing in place, are ignored.
+    pub fn handle_event(
+        &mut self,
+        event: &crossterm::event::Event,
+        frame: &str,
+        out: &mut impl Write,
+        state: &mut WriteState,
+    ) -> io::Result<()> {
+        let crossterm::event::Event::Resize(columns, _) = *event else {
+            return Ok(());
+        };
+        if !self.in_place {
+            return Ok(());
+        }
+        let columns = usize::from(columns).max(1);
+
+        if self.ansi {
+            let written = self.prepare(frame, &[], state).width();
+            let reflowed_rows = written.div_ceil(columns).saturating_sub(1);
+            if reflowed_rows > 0 {
+                write!(out, "\x1b[{reflowed_rows}A")?;
+            }
+            // Clear from the start of the frame to the end of the screen.
+            write!(out, "\r\x1b[J")?;
+        }
+
+        self.width = Some(columns);
+        write_frame(frame, out, self, state)
+    }
+}
+
+/// Whether the terminal understands the ANSI escape codes used to update frames in place.
+fn ansi_support() -> bool {
+    #[cfg(all(windows, feature = "crossterm"))]
+    let supported = crossterm::ansi_support::supports_ansi();
+    // Enabling them in the Windows console needs the console API, which only crossterm calls.
+    #[cfg(all(windows, not(feature = "crossterm")))]
+    let supported = false;
+    #[cfg(not(windows))]
+    let supported = true;
+    supported
+}
+
+/// Write the current value of `morph` and then every frame until the morph completes, each shown
+/// for its [`MorphingString::next_step_duration`] of the interval.
+///
+/// In place updates return the cursor to the start of the line and clear it, so they only work for
+/// values without newlines. The line is left unterminated so the next morph can overwrite it too.
+pub fn write_frames(
+    morph: &mut MorphingString,
+    out: &mut impl Write,
+    options: &WriteOptions,
+) -> io::Result<()> {
+    // Only masked frames need the highlights, to mark the edited chars.
+    let highlights = |morph: &MorphingString| match options.mask {
+        Some(_) => morph.highlights(),
+        None => Vec::new(),
+    };
+    let mut state = WriteState::default();
+    write_highlighted(morph.value(), &highlights(morph), out, options, &mut state)?;
+    let mut pacer = FramePacer::from_interval(options.interval);
+    while !morph.progress().is_complete() {
+        pacer.set_interval(morph.next_step_duration(options.interval));
+        pacer.wait_for_next_frame();
+        morph.advance();
+        write_highlighted(morph.value(), &highlights(morph), out, options, &mut state)?;
+    }
+    Ok(())
+}
+
+/// Write a single `frame`, replacing the previous one when updating in place, and flush `out`.
+/// `state` carries over what's needed of the previous frames written to `out`.
+pub fn write_frame(
+    frame: &str,
+    out: &mut impl Write,
+    options: &WriteOptions,
+    state: &mut WriteState,
+) -> io::Result<()> {
+    write_highlighted(frame, &[], out, options, state)
+}
+
+/// [`write_frame`] marking the chars `highlights` mark as edited when masking.
+fn write_highlighted(
+    frame: &str,
+    highlights: &[Option<Highlight>],
+    out: &mut impl Write,
+    options: &WriteOptions,
+    state: &mut WriteState,
+) -> io::Result<()> {
+    let frame = options.prepare(frame, highlights, state);
+    if options.in_place && options.ansi {
+        // Return to the start of the line and clear it.
+        write!(out, "\r\x1b[2K{frame}")?;
+    } else if options.in_place {
+        // Blank whatever is left of the previous frame and move the cursor back to the end of
+        // this one.
+        let width = frame.width();
+        let leftover = std::mem::replace(&mut state.written, width).saturating_sub(width);
+        write!(
+            out,
+            "\r{frame}{}{}",
+            " ".repeat(leftover),
+            "\x08".repeat(leftover)
+        )?;
+    } else {
+        writeln!(out, "{frame}")?;
+    }
+    out.flush()
+}
+
+/// A frame of a morph along with how its chars change around it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub value: String,
+    /// The highlight of every char of the value. See [`MorphingString::highlights`].
+    pub highlights: Vec<Option<Highlight>>,
+    /// The edits that produced the value from the previous frame. See
+    /// [`MorphingString::last_edits`].
+    pub last_edits: Vec<Edit>,
+}
+
+impl Frame {
+    /// The current frame of `morph`.
+    pub fn of(morph: &MorphingString) -> Self {
+        Self {
+            value: morph.value().to_string(),
+            highlights: morph.highlights(),
+            last_edits: morph.last_edits().to_vec(),
+        }
+    }
+}
+
+/// An output frames of morphs are rendered to.
+pub trait Renderer {
+    /// Prepare the output before the first frame.
+    fn begin(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn render_frame(&mut self, frame: &Frame) -> io::Result<()>;
+
+    /// Finish the output after the last frame.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Render the current value of `morph` and then every frame until the morph completes, each shown
+/// for its [`MorphingString::next_step_duration`] of `interval`.
+pub fn render_frames(
+    morph: &mut MorphingString,
+    renderer: &mut impl Renderer,
+    interval: Duration,
+) -> io::Result<()> {
+    renderer.begin()?;
+    renderer.render_frame(&Frame::of(morph))?;
+    let mut pacer = FramePacer::from_interval(interval);
+    while !morph.progress().is_complete() {
+        pacer.set_interval(morph.next_step_duration(interval));
+        pacer.wait_for_next_frame();
+        morph.advance();
+        renderer.render_frame(&Frame::of(morph))?;
+    }
+    renderer.finish()
+}
+
+/// Renders a line per frame.
+#[derive(Debug)]
+pub struct PlainWriter<W> {
+    out: W,
+    options: WriteOptions,
+    state: WriteState,
+}
+
+impl<W: Write> PlainWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            options: WriteOptions::default().without_in_place(),
+            state: WriteState::default(),
+        }
+    }
+
+    /// Truncate frames to `width` terminal columns. See [`WriteOptions::with_width`].
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.options = self.options.with_width(width);
+        self
+    }
+
+    /// Mask every char of the frames. See [`WriteOptions::with_mask`].
+    pub fn with_mask(mut self, mask: char) -> Self {
+        self.options = self.options.with_mask(mask);
+        self
+    }
+
+    /// Mask the chars the last step edited with `mask`. See [`WriteOptions::with_edit_mask`].
+    pub fn with_edit_mask(mut self, mask: char) -> Self {
+        self.options = self.options.with_edit_mask(mask);
+        self
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> Renderer for PlainWriter<W> {
+    fn render_frame(&mut self, frame: &Frame) -> io::Result<()> {
+        write_highlighted(
+            &frame.value,
+            &frame.highlights,
+            &mut self.out,
+            &self.options,
+            &mut self.state,
+        )
+    }
+}
+
+/// Renders frames in place on a single line of a terminal, truncated to its width.
+#[cfg(feature = "crossterm")]
+#[derive(Debug)]
+pub struct CrosstermInline<W> {
+    out: W,
+    options: WriteOptions,
+    state: WriteState,
+    // The last rendered frame, written again when the terminal is resized.
+    last: String,
+}
+
+#[cfg(feature = "crossterm")]
+impl<W: Write + IsTerminal> CrosstermInline<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            options: WriteOptions::for_terminal(&out),
+            out,
+            state: WriteState::default(),
+            last: String::new(),
+        }
+    }
+
+    /// Follow resizes of the terminal. See [`WriteOptions::handle_event`].
+    pub fn handle_event(&mut self, event: &crossterm::event::Event) -> io::Result<()> {
+        self.options
+            .handle_event(event, &self.last, &mut self.out, &mut self.state)
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl<W: Write> Renderer for CrosstermInline<W> {
+    fn render_frame(&mut self, frame: &Frame) -> io::Result<()> {
+        self.last.clone_from(&frame.value);
+        write_highlighted(
+            &frame.value,
+            &frame.highlights,
+            &mut self.out,
+            &self.options,
+            &mut self.state,
+        )
+    }
+
+    /// Terminate the line so later output doesn't overwrite the last frame.
+    fn finish(&mut self) -> io::Result<()> {
+        writeln!(self.out)
+    }
+}
+
+/// Renders frames in place on a single line of a [`console::Term`], truncated to its width, and a
+/// line per frame when it isn't a terminal. An alternative to `CrosstermInline` for CLIs built
+/// on `console` or `indicatif`.
+#[cfg(feature = "console")]
+#[derive(Debug)]
+pub struct ConsoleTerm {
+    term: console::Term,
+    options: WriteOptions,
+    state: WriteState,
+}
+
+#[cfg(feature = "console")]
+impl ConsoleTerm {
+    pub fn new(term: console::Term) -> Self {
+        let options = if term.is_term() {
+            WriteOptions::default().with_width(term.size().1.into())
+        } else {
+            WriteOptions::default().without_in_place()
+        };
+        Self {
+            term,
+            options,
+            state: WriteState::default(),
+        }
+    }
+
+    /// End truncated frames with `ellipsis`. See [`WriteOptions::with_ellipsis`].
+    pub fn with_ellipsis(mut self, ellipsis: impl Into<String>) -> Self {
+        self.options = self.options.with_ellipsis(ellipsis);
+        self
+    }
+}
+
+#[cfg(feature = "console")]
+impl Renderer for ConsoleTerm {
+    fn render_frame(&mut self, frame: &Frame) -> io::Result<()> {
+        let frame = self
+            .options
+            .prepare(&frame.value, &frame.highlights, &mut self.state);
+        if self.options.in_place {
+            self.term.clear_line()?;
+            self.term.write_str(&frame)?;
+        } else {
+            self.term.write_line(&frame)?;
+        }
+        self.term.flush()
+    }
+
+    /// Terminate the line so later output doesn't overwrite the last frame.
+    fn finish(&mut self) -> io::Result<()> {
+        if self.options.in_place {
+            self.term.write_line("")?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders frames onto a row of a [`termwiz::surface::Surface`], coloring inserted, substituted
+/// and deleting chars green, yellow and red respectively by default. Draw the surface onto a terminal to
+/// show the frames.
+#[cfg(feature = "termwiz")]
+pub struct TermwizSurface {
+    surface: termwiz::surface::Surface,
+    row: usize,
+    style: MorphStyle<termwiz::color::ColorAttribute>,
+    fade: Fade,
+}
+
+#[cfg(feature = "termwiz")]
+impl TermwizSurface {
+    pub fn new(surface: termwiz::surface::Surface) -> Self {
+        use termwiz::color::{AnsiColor, ColorAttribute};
+
+        Self {
+            surface,
+            row: 0,
+            style: MorphStyle::new(
+                AnsiColor::Green.into(),
+                AnsiColor::Yellow.into(),
+                AnsiColor::Red.into(),
+                ColorAttribute::Default,
+            ),
+            fade: Fade::default(),
+        }
+    }
+
+    /// Color the chars with the foreground colors of `style` instead.
+    pub fn with_style(mut self, style: MorphStyle<termwiz::color::ColorAttribute>) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Render onto `row` of the surface rather than the first one.
+    pub fn with_row(mut self, row: usize) -> Self {
+        self.row = row;
+        self
+    }
+
+    pub fn surface(&self) -> &termwiz::surface::Surface {
+        &self.surface
+    }
+
+    pub fn into_inner(self) -> termwiz::surface::Surface {
+        self.surface
+    }
+}
+
+#[cfg(feature = "termwiz")]
+impl Renderer for TermwizSurface {
+    fn render_frame(&mut self, frame: &Frame) -> io::Result<()> {
+        use termwiz::{
+            cell::{AttributeChange, CellAttributes},
+            color::ColorAttribute,
+            surface::{Change, Position},
+        };
+
+        let highlights = self.fade.update(frame, self.style.persist);
+        let mut changes = vec![
+            Change::CursorPosition {
+                x: Position::Absolute(0),
+                y: Position::Absolute(self.row),
+            },
+            Change::ClearToEndOfLine(ColorAttribute::Default),
+        ];
+        for (highlight, run) in runs(&frame.value, &highlights) {
+            let color = *self.style.get(highlight);
+            changes.push(Change::Attribute(AttributeChange::Foreground(color)));
+            changes.push(Change::Text(run.to_string()));
+        }
+        changes.push(Change::AllAttributes(CellAttributes::default()));
+
+        self.surface.add_changes(changes);
+        Ok(())
+    }
+}
+
+/// Renders frames as `<pre>` elements of a `<div class="morph">`, wrapping inserted, substituted
+/// and deleting chars in `<ins>`, `<mark>` and `<del>` respectively by default.
+#[derive(Debug)]
+pub struct Html<W> {
+    out: W,
+    style: MorphStyle<&'static str>,
+    fade: Fade,
+}
+
+impl<W: Write> Html<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            style: MorphStyle::new("ins", "mark", "del", ""),
+            fade: Fade::default(),
+        }
+    }
+
+    /// Wrap the chars in the tags named by `style` instead, leaving those styled with an empty name
+    /// unwrapped.
+    pub fn with_style(mut self, style: MorphStyle<&'static str>) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> Renderer for Html<W> {
+    fn begin(&mut self) -> io::Result<()> {
+        writeln!(self.out, "<div class=\"morph\">")
+    }
+
+    fn render_frame(&mut self, frame: &Frame) -> io::Result<()> {
+        let highlights = self.fade.update(frame, self.style.persist);
+
+        write!(self.out, "<pre>")?;
+        for (highlight, run) in runs(&frame.value, &highlights) {
+            let tag = *self.style.get(highlight);
+            if !tag.is_empty() {
+                write!(self.out, "<{tag}>")?;
+            }
+            for c in run.chars() {
+                match c {
+                    '&' => write!(self.out, "&amp;")?,
+                    '<' => write!(self.out, "&lt;")?,
+                    '>' => write!(self.out, "&gt;")?,
+                    c => write!(self.out, "{c}")?,
+                }
+            }
+            if !tag.is_empty() {
+                write!(self.out, "</{tag}>")?;
+            }
+        }
+        writeln!(self.out, "</pre>")
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        writeln!(self.out, "</div>")?;
+        self.out.flush()
+    }
+}
+
+/// The styles [`AnsiStyler`] highlights changed chars with.
+#[cfg(feature = "anstyle")]
+pub type HighlightStyles = MorphStyle<anstyle::Style>;
+
+/// Styles successive frames of a morph with ANSI escape sequences, keeping changed chars styled
+/// for [`MorphStyle::persist`] frames. Keep one per morph and style every frame with it.
+#[cfg(feature = "anstyle")]
+#[derive(Debug, Clone, Default)]
+pub struct AnsiStyler {
+    styles: HighlightStyles,
+    fade: Fade,
+}
+
+#[cfg(feature = "anstyle")]
+impl AnsiStyler {
+    pub fn new(styles: HighlightStyles) -> Self {
+        Self {
+            styles,
+            fade: Fade::default(),
+        }
+    }
+
+    /// The current value of `morph` with the chars recently inserted or substituted, and those
+    /// the next advance deletes, wrapped in ANSI escape sequences of the respective styles. The
+    /// escape sequences would get cut by truncation so write styled frames without a width.
+    pub fn styled_frame(&mut self, morph: &MorphingString) -> String {
+        let frame = Frame::of(morph);
+        let highlights = self.fade.update(&frame, self.styles.persist);
+
+        let mut styled = String::with_capacity(frame.value.len());
+        for (highlight, run) in runs(&frame.value, &highlights) {
+            let style = self.styles.get(highlight);
+            styled.push_str(&style.render().to_string());
+            styled.push_str(run);
+            styled.push_str(&style.render_reset().to_string());
+        }
+        styled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_place_frames_overwrite_the_line() {
+        let mut morph = MorphingString::new("ab".to_string());
+        morph.set_target("b".to_string());
+        let mut out = Vec::new();
+
+        write_frames(&mut morph, &mut out, &WriteOptions::default()).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "\r\x1b[2Kab\r\x1b[2Kb");
+    }
+
+    #[test]
+    fn without_ansi_frames_are_overwritten_with_spaces() {
+        let mut out = Vec::new();
+        let options = WriteOptions::default().without_ansi();
+        let mut state = WriteState::default();
+
+        write_frame("abc", &mut out, &options, &mut state).unwrap();
+        write_frame("a\r", &mut out, &options, &mut state).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "\rabc\ra  \x08\x08");
+    }
+
+    #[test]
+    fn carriage_returns_are_dropped_before_clearing() {
+        let mut out = Vec::new();
+
+        let options = WriteOptions::default();
+        write_frame("ab\r", &mut out, &options, &mut WriteState::default()).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "\r\x1b[2Kab");
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn terminals_support_ansi_outside_windows() {
+        assert!(ansi_support());
+    }
+
+    #[cfg(all(windows, not(feature = "crossterm")))]
+    #[test]
+    fn windows_consoles_fall_back_to_spaces_without_crossterm() {
+        assert!(!ansi_support());
+    }
+
+    #[cfg(feature = "termwiz")]
+    #[test]
+    fn termwiz_surface_shows_the_last_frame() {
+        let mut morph = MorphingString::new("abc".to_string());
+        morph.set_target("x".to_string());
+        let mut renderer = TermwizSurface::new(termwiz::surface::Surface::new(4, 2)).with_row(1);
+
+        render_frames(&mut morph, &mut renderer, Duration::ZERO).unwrap();
+
+        assert_eq!(renderer.surface().screen_chars_to_string(), "    \nx   \n");
+    }
+
+    #[test]
+    fn pipes_get_a_line_per_frame() {
+        let mut morph = MorphingString::new("ab".to_string());
+        morph.set_target("b".to_string());
+        let mut out = Vec::new();
+
+        let options = WriteOptions::default().without_in_place();
+        write_frames(&mut morph, &mut out, &options).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "ab\nb\n");
+    }
+
+    #[test]
+    fn frames_are_truncated_to_width() {
+        let mut out = Vec::new();
+        let options = WriteOptions::default().without_in_place().with_width(3);
+        let mut state = WriteState::default();
+
+        write_frame("kočka", &mut out, &options, &mut state).unwrap();
+        write_frame("ko", &mut out, &options, &mut state).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "koč\nko\n");
+    }
+
+    #[test]
+    fn truncation_counts_columns_and_ends_with_ellipsis() {
+        let options = WriteOptions::default().with_width(5).with_ellipsis("…");
+
+        assert_eq!(options.truncate("kočka"), "kočka");
+        assert_eq!(options.truncate("kočička"), "koči…");
+        // Wide chars take two columns each.
+        assert_eq!(options.truncate("日本語の文"), "日本…");
+        assert_eq!(options.with_width(0).truncate("abc"), "");
+    }
+
+    #[test]
+    fn masked_frames_hide_the_content() {
+        let mut morph = MorphingString::new("pass".to_string());
+        morph.set_target("secret".to_string());
+        let mut renderer = PlainWriter::new(Vec::new()).with_mask('•');
+
+        render_frames(&mut morph, &mut renderer, Duration::ZERO).unwrap();
+
+        let out = String::from_utf8(renderer.into_inner()).unwrap();
+        assert!(out.chars().all(|c| c == '•' || c == '◦' || c == '\n'));
+        let frames: Vec<_> = out.lines().collect();
+        assert_eq!(frames[0], "••••");
+        assert_eq!(frames.last().unwrap().chars().count(), 6);
+        // "pass" and "secret" differ in every char, so some step substitutes and marks its chars.
+        assert!(
+            frames.windows(2).any(
+                |pair| pair[0].chars().count() == pair[1].chars().count() && pair[0] != pair[1]
+            )
+        );
+    }
+
+    #[test]
+    fn plain_writer_renders_a_line_per_frame() {
+        let mut morph = MorphingString::new("ab".to_string());
+        morph.set_target("b".to_string());
+        let mut renderer = PlainWriter::new(Vec::new());
+
+        render_frames(&mut morph, &mut renderer, Duration::ZERO).unwrap();
+
+        assert_eq!(String::from_utf8(renderer.into_inner()).unwrap(), "ab\nb\n");
+    }
+
+    #[test]
+    fn html_marks_up_changes() {
+        let mut morph = MorphingString::new("a<b".to_string());
+        morph.set_target("x<".to_string());
+        let mut renderer = Html::new(Vec::new());
+
+        render_frames(&mut morph, &mut renderer, Duration::ZERO).unwrap();
+
+        assert_eq!(
+            String::from_utf8(renderer.into_inner()).unwrap(),
+            "<div class=\"morph\">\n\
+             <pre>a&lt;b</pre>\n\
+             <pre><mark>x</mark>&lt;<del>b</del></pre>\n\
+             <pre>x&lt;</pre>\n\
+             </div>\n"
+        );
+    }
+
+    #[test]
+    fn tabs_expand_to_tab_stops() {
+        let options = WriteOptions::default().with_tab_width(4);
+        let state = &mut WriteState::default();
+
+        assert_eq!(options.prepare("a\tb", &[], state), "a   b");
+        assert_eq!(
+            options.prepare("日本\tx\n\ty", &[], state),
+            "日本    x\n    y"
+        );
+        assert_eq!(
+            options.with_width(6).prepare("ab\tcd", &[], state),
+            "ab  cd"
+        );
+    }
+
+    #[test]
+    fn trailing_whitespace_is_stripped_or_padded() {
+        let strip = WriteOptions::default().with_trailing_whitespace(TrailingWhitespace::Strip);
+        let pad = WriteOptions::default().with_trailing_whitespace(TrailingWhitespace::Pad);
+
+        let state = &mut WriteState::default();
+
+        assert_eq!(strip.prepare("ab \t", &[], state), "ab");
+        assert_eq!(pad.prepare("abc", &[], state), "abc");
+        assert_eq!(pad.prepare("a", &[], state), "a  ");
+        assert_eq!(pad.prepare("abcd", &[], state), "abcd");
+        assert_eq!(pad.prepare("", &[], state), "    ");
+        // A new output starts padding afresh.
+        assert_eq!(pad.prepare("a", &[], &mut WriteState::default()), "a");
+    }
+
+    #[cfg(feature = "anstyle")]
+    #[test]
+    fn styled_frame_wraps_changed_chars() {
+        let styles = HighlightStyles::default();
+        let mut styler = AnsiStyler::new(styles);
+        let mut morph = MorphingString::new("cat".to_string());
+        morph.set_target("chart".to_string());
+        morph.advance();
+
+        assert_eq!(
+            styler.styled_frame(&morph),
+            format!(
+                "c{}h{}at",
+                styles.inserted.render(),
+                styles.inserted.render_reset()
+            )
+        );
+    }
+
+    #[cfg(feature = "anstyle")]
+    #[test]
+    fn styled_frames_keep_changed_chars_styled_while_they_persist() {
+        let styles = HighlightStyles::default().with_persist(2);
+        let mut styler = AnsiStyler::new(styles);
+        let mut morph = MorphingString::new("cat".to_string());
+        morph.set_target("chart".to_string());
+
+        let frames: Vec<_> = std::iter::from_fn(|| {
+            (!morph.progress().is_complete()).then(|| {
+                morph.advance();
+                styler.styled_frame(&morph)
+            })
+        })
+        .collect();
+
+        let inserted = |c| {
+            format!(
+                "{}{c}{}",
+                styles.inserted.render(),
+                styles.inserted.render_reset()
+            )
+        };
+        assert_eq!(
+            frames,
+            [
+                format!("c{}at", inserted('h')),
+                format!("c{}a{}t", inserted('h'), inserted('r')),
+            ]
+        );
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn resize_clears_reflowed_rows_and_truncates() {
+        let mut out = Vec::new();
+        let mut options = WriteOptions::default().with_width(10);
+        let resize = crossterm::event::Event::Resize(4, 24);
+
+        options
+            .handle_event(
+                &resize,
+                "the quick fox",
+                &mut out,
+                &mut WriteState::default(),
+            )
+            .unwrap();
+
+        // The 10 written chars got reflowed into 3 rows of 4.
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[2A\r\x1b[J\r\x1b[2Kthe "
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use crate::edit::Edit;
+
+/// What [`MorphingString::set_target`](crate::MorphingString::set_target) does when a morph is
+/// still in progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetargetPolicy {
+    /// Drop the remaining edits and morph from the current value right away.
+    #[default]
+    Replace,
+    /// Finish the current morph first. Only the latest queued target is kept.
+    Queue,
+    /// Finish the word the last advance edited, then morph from there. Avoids visibly changing
+    /// direction mid-word.
+    Merge,
+}
+
+/// The number of `remaining` edits that finish the word of `value` the edits of `last_step`
+/// touched, i.e. the edits up to the first one outside of it.
+pub(crate) fn word_in_progress(
+    value: &str,
+    last_step: &[Edit],
+    remaining: impl IntoIterator<Item = Edit>,
+) -> usize {
+    let Some(last) = last_step.last() else {
+        return 0;
+    };
+    let chars: Vec<char> = value.chars().collect();
+    let index = last.index().min(chars.len());
+
+    let mut start = index;
+    while start > 0 && !chars[start - 1].is_whitespace() {
+        start -= 1;
+    }
+    let mut end = index;
+    while end < chars.len() && !chars[end].is_whitespace() {
+        end += 1;
+    }
+
+    let mut count = 0;
+    for edit in remaining {
+        let index = edit.index();
+        match edit {
+            Edit::Insert { .. } if (start..=end).contains(&index) => end += 1,
+            Edit::Delete { .. } if (start..end).contains(&index) => end -= 1,
+            Edit::Substitute { .. } if (start..end).contains(&index) => {}
+            _ => break,
+        }
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+    use Edit::*;
+
+    #[test]
+    fn edits_up_to_the_next_word_finish_the_current_one() {
+        // "ab cd" -> "xy cz", the first edit substituted 'a' already.
+        let remaining = VecDeque::from([
+            Substitute { c: 'y', index: 1 },
+            Substitute { c: 'z', index: 4 },
+        ]);
+
+        assert_eq!(
+            word_in_progress(
+                "xb cd",
+                &[Substitute { c: 'x', index: 0 }],
+                remaining.clone()
+            ),
+            1
+        );
+        assert_eq!(word_in_progress("xb cd", &[], remaining), 0);
+    }
+}
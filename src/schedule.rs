@@ -0,0 +1,250 @@
+use crate::edit::Edit;
+
+/// The edits of a script pinned to the characters (slots) they touch rather than to indexes, so
+/// they can be applied in a different order than planned while still producing the same result.
+///
+/// Slots are all the characters of the start string plus all the inserted ones, in the order they
+/// appear in the evolving string. An edit's index at the time it's applied is the number of slots
+/// before it that are present at that time.
+#[derive(Debug, Clone)]
+pub(crate) struct Schedule {
+    // (slot, edit) in the originally planned order.
+    edits: Vec<(usize, Edit)>,
+    // The last char every slot holds, i.e. its char in the target or the char that gets deleted.
+    last_chars: Vec<char>,
+    present: PresenceCounter,
+}
+
+impl Schedule {
+    pub fn new<'a>(start: &str, edits: impl IntoIterator<Item = &'a Edit>) -> Self {
+        // Simulate the script keeping track of which slot is at which index. Slots are identified
+        // by the order they were created in until they are ranked at the end.
+        let mut chars: Vec<char> = start.chars().collect();
+        let start_len = chars.len();
+        let mut current: Vec<usize> = (0..start_len).collect();
+        let mut order: Vec<usize> = (0..start_len).collect();
+        let mut pinned = Vec::new();
+
+        for edit in edits {
+            let slot = match *edit {
+                Edit::Insert { c, index } => {
+                    let slot = order.len();
+                    chars.push(c);
+                    match current.get(index) {
+                        Some(next) => {
+                            let position = order
+                                .iter()
+                                .position(|slot| slot == next)
+                                .expect("present slots are ordered");
+                            order.insert(position, slot);
+                        }
+                        None => order.push(slot),
+                    }
+                    current.insert(index, slot);
+                    slot
+                }
+                Edit::Delete { index } => current.remove(index),
+                Edit::Substitute { c, index } => {
+                    chars[current[index]] = c;
+                    current[index]
+                }
+            };
+            pinned.push((slot, *edit));
+        }
+
+        let mut rank = vec![0; order.len()];
+        let mut last_chars = vec![' '; order.len()];
+        for (position, slot) in order.iter().enumerate() {
+            rank[*slot] = position;
+            last_chars[position] = chars[*slot];
+        }
+
+        let mut present = PresenceCounter::new(order.len());
+        for &start_slot in &rank[..start_len] {
+            present.set(start_slot, true);
+        }
+
+        Self {
+            edits: pinned
+                .into_iter()
+                .map(|(slot, edit)| (rank[slot], edit))
+                .collect(),
+            last_chars,
+            present,
+        }
+    }
+
+    /// The position of the char edit `id` touches, relative to the chars the others touch.
+    pub fn slot(&self, id: usize) -> usize {
+        self.edits[id].0
+    }
+
+    /// The number of slots, i.e. chars of the start string plus inserted chars.
+    pub fn slot_count(&self) -> usize {
+        self.last_chars.len()
+    }
+
+    /// The last char `slot` holds: its char in the target or the char that gets deleted.
+    pub fn last_char(&self, slot: usize) -> char {
+        self.last_chars[slot]
+    }
+
+    /// Edit `id` with its index adjusted to the current state of the string.
+    pub fn resolve(&self, id: usize) -> Edit {
+        let (slot, edit) = self.edits[id];
+        let index = self.present.count_before(slot);
+
+        match edit {
+            Edit::Insert { c, .. } => Edit::Insert { c, index },
+            Edit::Delete { .. } => Edit::Delete { index },
+            Edit::Substitute { c, .. } => Edit::Substitute { c, index },
+        }
+    }
+
+    /// Resolve edit `id` and update the state of the string as if it was applied.
+    pub fn apply(&mut self, id: usize) -> Edit {
+        let edit = self.resolve(id);
+        match edit {
+            Edit::Insert { .. } => self.present.set(self.edits[id].0, true),
+            Edit::Delete { .. } => self.present.set(self.edits[id].0, false),
+            Edit::Substitute { .. } => {}
+        }
+        edit
+    }
+
+    /// Undo [`Schedule::apply`] of edit `id`, the last edit applied.
+    pub fn undo(&mut self, id: usize) {
+        let (slot, edit) = self.edits[id];
+        match edit {
+            Edit::Insert { .. } => self.present.set(slot, false),
+            Edit::Delete { .. } => self.present.set(slot, true),
+            Edit::Substitute { .. } => {}
+        }
+    }
+
+    /// Resolve all the edits in the given `order`, which must be a permutation of edit ids that
+    /// keeps edits touching the same slot in their original relative order.
+    #[cfg(test)]
+    pub fn sequence(
+        mut self,
+        order: impl IntoIterator<Item = usize>,
+    ) -> std::collections::VecDeque<Edit> {
+        order.into_iter().map(|id| self.apply(id)).collect()
+    }
+}
+
+/// A Fenwick tree counting present slots before a given one in logarithmic time.
+#[derive(Debug, Clone)]
+struct PresenceCounter {
+    tree: Vec<isize>,
+    present: Vec<bool>,
+}
+
+impl PresenceCounter {
+    fn new(len: usize) -> Self {
+        Self {
+            tree: vec![0; len + 1],
+            present: vec![false; len],
+        }
+    }
+
+    fn set(&mut self, slot: usize, present: bool) {
+        if self.present[slot] == present {
+            return;
+        }
+        self.present[slot] = present;
+
+        let delta = if present { 1 } else { -1 };
+        let mut i = slot + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn count_before(&self, slot: usize) -> usize {
+        let mut count = 0;
+        let mut i = slot;
+        while i > 0 {
+            count += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        count as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levenshtein::compute_edit_sequence;
+
+    fn apply_all<'a>(start: &str, edits: impl IntoIterator<Item = &'a Edit>) -> String {
+        let mut string = start.to_string();
+        for edit in edits {
+            string = edit.apply(&string);
+        }
+        string
+    }
+
+    #[test]
+    fn original_order_reproduces_script() {
+        let edits = compute_edit_sequence("abcdef", "xazced");
+        let schedule = Schedule::new("abcdef", &edits);
+
+        assert_eq!(schedule.sequence(0..edits.len()), edits);
+    }
+
+    #[test]
+    fn any_order_produces_target() {
+        let pairs = [
+            ("abcdef", "xazced"),
+            ("sunday", "saturday"),
+            ("kitten", "sitting"),
+            ("daskdas dasd sadjasnd", "nfad ad f sasdkmfpsmdfasM"),
+        ];
+
+        for (start, target) in pairs {
+            let edits = compute_edit_sequence(start, target);
+            let len = edits.len();
+
+            let reversed = Schedule::new(start, &edits).sequence((0..len).rev());
+            assert_eq!(apply_all(start, &reversed), target, "{start} reversed");
+
+            let interleaved = (0..len).step_by(2).chain((1..len).step_by(2));
+            let interleaved = Schedule::new(start, &edits).sequence(interleaved);
+            assert_eq!(
+                apply_all(start, &interleaved),
+                target,
+                "{start} interleaved"
+            );
+        }
+    }
+
+    #[test]
+    fn undone_edits_resolve_as_before() {
+        let edits = compute_edit_sequence("kitten", "sitting");
+        let mut schedule = Schedule::new("kitten", &edits);
+        let before: Vec<_> = (0..edits.len()).map(|id| schedule.resolve(id)).collect();
+
+        let last = edits.len() - 1;
+        schedule.apply(last);
+        schedule.apply(0);
+        schedule.undo(0);
+        schedule.undo(last);
+
+        assert!((0..edits.len()).map(|id| schedule.resolve(id)).eq(before));
+    }
+
+    #[test]
+    fn slots_follow_string_order() {
+        let edits = [Edit::Insert { c: 'x', index: 0 }, Edit::Delete { index: 2 }];
+        let schedule = Schedule::new("abc", &edits);
+
+        // Inserting x at the front comes before deleting b.
+        assert!(schedule.slot(0) < schedule.slot(1));
+        let last_chars: String = (0..schedule.slot_count())
+            .map(|slot| schedule.last_char(slot))
+            .collect();
+        assert_eq!(last_chars, "xabc");
+    }
+}
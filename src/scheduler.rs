@@ -0,0 +1,208 @@
+use std::{
+    sync::mpsc::Sender,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use crate::MorphingString;
+
+/// Identifies a morph registered with a [`MorphScheduler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MorphId(usize);
+
+/// Owns many morphs and advances each at its own interval from a single thread, delivering every
+/// new frame to the callback it was registered with.
+#[derive(Default)]
+pub struct MorphScheduler {
+    entries: Vec<Entry>,
+    next_id: usize,
+}
+
+struct Entry {
+    id: MorphId,
+    morph: MorphingString,
+    interval: Duration,
+    // When the morph is due to advance next. Morphs with nothing to do aren't advanced.
+    next_tick: Option<Instant>,
+    on_frame: Box<OnFrame>,
+}
+
+type OnFrame = dyn FnMut(MorphId, &str) + Send;
+
+impl MorphScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance `morph` every `interval`, stretched by its
+    /// [`MorphingString::next_step_duration`], while it's in progress, calling `on_frame` with
+    /// every new frame.
+    pub fn register(
+        &mut self,
+        morph: MorphingString,
+        interval: Duration,
+        on_frame: impl FnMut(MorphId, &str) + Send + 'static,
+    ) -> MorphId {
+        let id = MorphId(self.next_id);
+        self.next_id += 1;
+        self.entries.push(Entry {
+            id,
+            morph,
+            interval,
+            next_tick: None,
+            on_frame: Box::new(on_frame),
+        });
+        id
+    }
+
+    /// Like [`MorphScheduler::register`] but sending every new frame to `frames`, e.g. shared by
+    /// all the morphs rendered by another thread. Frames are dropped once the receiver is gone.
+    pub fn register_with_sender(
+        &mut self,
+        morph: MorphingString,
+        interval: Duration,
+        frames: Sender<(MorphId, String)>,
+    ) -> MorphId {
+        self.register(morph, interval, move |id, frame| {
+            let _ = frames.send((id, frame.to_string()));
+        })
+    }
+
+    /// Stop scheduling the morph and hand it back.
+    pub fn unregister(&mut self, id: MorphId) -> Option<MorphingString> {
+        let position = self.entries.iter().position(|entry| entry.id == id)?;
+        Some(self.entries.remove(position).morph)
+    }
+
+    /// The morph, e.g. to give it a new target. It starts advancing at the next tick.
+    pub fn morph_mut(&mut self, id: MorphId) -> Option<&mut MorphingString> {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.id == id)
+            .map(|entry| &mut entry.morph)
+    }
+
+    /// Advance every morph in progress that's due at `now` and deliver its new frame. Returns
+    /// when the next morph is due, or `None` when none is in progress.
+    pub fn tick(&mut self, now: Instant) -> Option<Instant> {
+        for entry in &mut self.entries {
+            if entry.morph.progress().is_complete() {
+                entry.next_tick = None;
+                continue;
+            }
+
+            let due = *entry.next_tick.get_or_insert(now);
+            if due > now {
+                continue;
+            }
+            entry.morph.advance();
+            (entry.on_frame)(entry.id, entry.morph.value());
+            // Keep to the interval without catching up on ticks missed by a late call.
+            let interval = entry.morph.next_step_duration(entry.interval);
+            let next = due + interval;
+            entry.next_tick = Some(if next <= now { now + interval } else { next });
+        }
+
+        self.entries
+            .iter()
+            .filter(|entry| !entry.morph.progress().is_complete())
+            .map(|entry| entry.next_tick.unwrap_or(now))
+            .min()
+    }
+
+    /// Tick, sleeping until the next morph is due, until all morphs complete.
+    pub fn run_until_idle(&mut self) {
+        while let Some(next_tick) = self.tick(Instant::now()) {
+            sleep(next_tick.saturating_duration_since(Instant::now()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+    use crate::EditWeights;
+
+    fn morph(start: &str, target: &str) -> MorphingString {
+        let mut morph = MorphingString::new(start.to_string());
+        morph.set_target(target.to_string());
+        morph
+    }
+
+    #[test]
+    fn morphs_advance_at_their_own_rates() {
+        let (sender, receiver) = mpsc::channel();
+        let mut scheduler = MorphScheduler::new();
+        let fast = scheduler.register_with_sender(
+            morph("", "abc"),
+            Duration::from_millis(10),
+            sender.clone(),
+        );
+        let slow =
+            scheduler.register_with_sender(morph("", "xy"), Duration::from_millis(20), sender);
+        let start = Instant::now();
+
+        assert_eq!(
+            scheduler.tick(start),
+            Some(start + Duration::from_millis(10))
+        );
+        assert_eq!(
+            scheduler.tick(start + Duration::from_millis(10)),
+            Some(start + Duration::from_millis(20))
+        );
+        assert_eq!(scheduler.tick(start + Duration::from_millis(20)), None);
+
+        let frames: Vec<_> = receiver.try_iter().collect();
+        assert_eq!(
+            frames,
+            vec![
+                (fast, "a".to_string()),
+                (slow, "x".to_string()),
+                (fast, "ab".to_string()),
+                (fast, "abc".to_string()),
+                (slow, "xy".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn edit_weights_stretch_ticks() {
+        let mut scheduler = MorphScheduler::new();
+        let weighted = MorphingString::new("abc".to_string()).with_edit_weights(EditWeights {
+            insert: 3,
+            delete: 1,
+            substitute: 1,
+        });
+        let id = scheduler.register(weighted, Duration::from_millis(10), |_, _| {});
+        scheduler
+            .morph_mut(id)
+            .unwrap()
+            .set_target("bcd".to_string());
+        let start = Instant::now();
+
+        // Inserting the `d` is next after deleting the `a`.
+        assert_eq!(
+            scheduler.tick(start),
+            Some(start + Duration::from_millis(30))
+        );
+        assert_eq!(scheduler.tick(start + Duration::from_millis(30)), None);
+    }
+
+    #[test]
+    fn retargeted_morphs_resume() {
+        let mut scheduler = MorphScheduler::new();
+        let id = scheduler.register(
+            MorphingString::new("a".to_string()),
+            Duration::ZERO,
+            |_, _| {},
+        );
+        let now = Instant::now();
+
+        assert_eq!(scheduler.tick(now), None);
+        scheduler.morph_mut(id).unwrap().set_target("b".to_string());
+        assert_eq!(scheduler.tick(now), None);
+        assert_eq!(scheduler.unregister(id).unwrap().value(), "b");
+    }
+}
@@ -0,0 +1,369 @@
+use std::{collections::VecDeque, error::Error, fmt, iter, num::TryFromIntError, slice, vec};
+
+use crate::{
+    constraint::WidthError,
+    edit::{Edit, PackedEdit, pack},
+    position::LineColumn,
+};
+
+/// A sequence of [`Edit`]s that turns one string into another when applied front to back.
+///
+/// The edits are stored packed, so indexes must fit in a `u32`. Collecting edits with larger
+/// indexes into a script panics, see [`EditScript::try_from_edits`] to handle them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditScript {
+    edits: Vec<PackedEdit>,
+}
+
+/// Iterator over the edits of an [`EditScript`].
+pub type Edits<'a> = iter::Map<slice::Iter<'a, PackedEdit>, fn(&PackedEdit) -> Edit>;
+
+/// Owning iterator over the edits of an [`EditScript`].
+pub type IntoEdits = iter::Map<vec::IntoIter<PackedEdit>, fn(PackedEdit) -> Edit>;
+
+impl EditScript {
+    /// Collect `edits` into a script, failing if an index doesn't fit in a `u32`. Collecting with
+    /// [`FromIterator`] panics instead.
+    pub fn try_from_edits(edits: impl IntoIterator<Item = Edit>) -> Result<Self, TryFromIntError> {
+        let edits = edits
+            .into_iter()
+            .map(PackedEdit::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { edits })
+    }
+
+    pub fn len(&self) -> usize {
+        self.edits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    pub fn iter(&self) -> Edits<'_> {
+        self.edits.iter().map(|edit| edit.unpack())
+    }
+
+    /// Apply all the edits onto `start`.
+    pub fn apply(&self, start: &str) -> String {
+        let mut string = start.to_string();
+        for edit in self {
+            let offset = edit.byte_offset(&string);
+            edit.apply_at(&mut string, offset);
+        }
+        string
+    }
+
+    /// Check that the edits apply to `start` one after the other and turn it into `target`.
+    pub(crate) fn check(&self, start: &str, target: &str) -> Result<(), ScriptError> {
+        let mut len = start.chars().count();
+        for (position, edit) in self.iter().enumerate() {
+            let in_bounds = match edit {
+                Edit::Insert { index, .. } => index <= len,
+                Edit::Delete { index } | Edit::Substitute { index, .. } => index < len,
+            };
+            if !in_bounds {
+                return Err(ScriptError::OutOfBounds { position });
+            }
+            match edit {
+                Edit::Insert { .. } => len += 1,
+                Edit::Delete { .. } => len -= 1,
+                Edit::Substitute { .. } => {}
+            }
+        }
+
+        if self.apply(start) != target {
+            return Err(ScriptError::WrongTarget);
+        }
+        Ok(())
+    }
+
+    /// An equivalent, possibly shorter script for the same `start`: a deletion followed by an
+    /// insertion at the same index becomes a substitution, edits undone or overwritten by the next
+    /// one are merged or dropped, and substitutions for the same char are dropped.
+    ///
+    /// Edits from the first one indexing past the end of the string it applies to on are only
+    /// merged, as what they substitute is unknown.
+    pub fn minify(&self, start: &str) -> EditScript {
+        let mut edits: Vec<Edit> = self.iter().collect();
+
+        loop {
+            let len = edits.len();
+            edits = merge_adjacent(edits);
+            edits = drop_no_ops(start, edits);
+            if edits.len() == len {
+                return edits.into_iter().collect();
+            }
+        }
+    }
+
+    /// Pair every edit with the UTF-8 byte offset it applies to in the evolving string that
+    /// starts out as `start`.
+    pub fn byte_offsets(&self, start: &str) -> impl Iterator<Item = (Edit, usize)> + '_ {
+        self.positions(start, |_, _| ())
+            .map(|(edit, offset, ())| (edit, offset))
+    }
+
+    /// Pair every edit with the [`LineColumn`] it applies to in the evolving string that starts out
+    /// as `start`. Inserted and deleted newlines shift the positions of subsequent edits.
+    pub fn line_columns(&self, start: &str) -> impl Iterator<Item = (Edit, LineColumn)> + '_ {
+        self.positions(start, LineColumn::at)
+            .map(|(edit, _, position)| (edit, position))
+    }
+
+    /// Walk the evolving string, mapping every edit to its byte offset and whatever `position`
+    /// computes from the string and offset before the edit is applied.
+    pub(crate) fn positions<'a, T>(
+        &'a self,
+        start: &str,
+        position: impl Fn(&str, usize) -> T + 'a,
+    ) -> impl Iterator<Item = (Edit, usize, T)> + 'a {
+        let mut string = start.to_string();
+        self.iter().map(move |edit| {
+            let offset = edit.byte_offset(&string);
+            let position = position(&string, offset);
+            edit.apply_at(&mut string, offset);
+            (edit, offset, position)
+        })
+    }
+}
+
+fn merge_adjacent(edits: Vec<Edit>) -> Vec<Edit> {
+    let mut merged: Vec<Edit> = Vec::with_capacity(edits.len());
+    for edit in edits {
+        let mut next = Some(edit);
+        // A merged edit may merge with the one before it again.
+        while let (Some(edit), Some(&last)) = (next, merged.last()) {
+            let Some(combined) = combine(last, edit) else {
+                break;
+            };
+            merged.pop();
+            next = combined;
+        }
+        merged.extend(next);
+    }
+    merged
+}
+
+/// The single edit equivalent to applying `first` and then `second`, `Some(None)` if they cancel
+/// out, or `None` if they can't be combined.
+fn combine(first: Edit, second: Edit) -> Option<Option<Edit>> {
+    use Edit::*;
+
+    if first.index() != second.index() {
+        return None;
+    }
+    let index = first.index();
+
+    match (first, second) {
+        (Delete { .. }, Insert { c, .. }) => Some(Some(Substitute { c, index })),
+        (Insert { .. }, Delete { .. }) => Some(None),
+        (Insert { .. }, Substitute { c, .. }) => Some(Some(Insert { c, index })),
+        (Substitute { .. }, Substitute { c, .. }) => Some(Some(Substitute { c, index })),
+        (Substitute { .. }, Delete { .. }) => Some(Some(Delete { index })),
+        _ => None,
+    }
+}
+
+/// Drop substitutions of chars for themselves, keeping every edit from the first out of bounds
+/// one on.
+fn drop_no_ops(start: &str, edits: Vec<Edit>) -> Vec<Edit> {
+    // The evolving string, until an edit doesn't apply to it.
+    let mut chars: Option<Vec<char>> = Some(start.chars().collect());
+    edits
+        .into_iter()
+        .filter(|edit| {
+            let Some(string) = &mut chars else {
+                return true;
+            };
+            match *edit {
+                Edit::Insert { c, index } if index <= string.len() => string.insert(index, c),
+                Edit::Delete { index } if index < string.len() => {
+                    string.remove(index);
+                }
+                Edit::Substitute { c, index } if index < string.len() => {
+                    return std::mem::replace(&mut string[index], c) != c;
+                }
+                _ => chars = None,
+            }
+            true
+        })
+        .collect()
+}
+
+/// Why an [`EditScript`] can't drive a morph from the current value to a target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptError {
+    /// The edit at `position` in the script indexes past the end of the string it applies to.
+    OutOfBounds { position: usize },
+    /// The script applies cleanly but doesn't produce the target.
+    WrongTarget,
+    /// No order of the edits keeps within the maximum width set by
+    /// [`MorphingString::with_max_width`](crate::MorphingString::with_max_width).
+    TooWide(WidthError),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptError::OutOfBounds { position } => {
+                write!(f, "edit {position} of the script is out of bounds")
+            }
+            ScriptError::WrongTarget => write!(f, "the script doesn't produce the target"),
+            ScriptError::TooWide(error) => {
+                write!(f, "the script can't keep within the width: {error}")
+            }
+        }
+    }
+}
+
+impl Error for ScriptError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScriptError::TooWide(error) => Some(error),
+            ScriptError::OutOfBounds { .. } | ScriptError::WrongTarget => None,
+        }
+    }
+}
+
+impl From<VecDeque<Edit>> for EditScript {
+    fn from(edits: VecDeque<Edit>) -> Self {
+        edits.into_iter().collect()
+    }
+}
+
+impl From<EditScript> for VecDeque<Edit> {
+    fn from(script: EditScript) -> Self {
+        script.into_iter().collect()
+    }
+}
+
+impl FromIterator<Edit> for EditScript {
+    fn from_iter<T: IntoIterator<Item = Edit>>(iter: T) -> Self {
+        Self {
+            edits: iter.into_iter().map(pack).collect(),
+        }
+    }
+}
+
+impl IntoIterator for EditScript {
+    type Item = Edit;
+    type IntoIter = IntoEdits;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.edits.into_iter().map(PackedEdit::unpack)
+    }
+}
+
+impl<'a> IntoIterator for &'a EditScript {
+    type Item = Edit;
+    type IntoIter = Edits<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff;
+    use Edit::*;
+
+    #[test]
+    fn oversized_indexes_fail_to_collect() {
+        let edits = [Insert { c: 'a', index: 0 }, Delete { index: 1 << 32 }];
+
+        assert!(EditScript::try_from_edits(edits).is_err());
+        assert_eq!(
+            EditScript::try_from_edits(edits.into_iter().take(1))
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn apply_produces_target() {
+        assert_eq!(diff("kočka", "kotě").apply("kočka"), "kotě");
+    }
+
+    #[test]
+    fn minify_merges_and_drops_redundant_edits() {
+        let script: EditScript = [
+            Delete { index: 0 },
+            Insert { c: 'x', index: 0 },
+            Insert { c: 'y', index: 1 },
+            Substitute { c: 'z', index: 1 },
+            Delete { index: 1 },
+            Substitute { c: 'c', index: 2 },
+        ]
+        .into_iter()
+        .collect();
+
+        let minified = script.minify("abc");
+
+        assert_eq!(
+            minified,
+            [Substitute { c: 'x', index: 0 }].into_iter().collect()
+        );
+        assert_eq!(minified.apply("abc"), script.apply("abc"));
+    }
+
+    #[test]
+    fn minify_keeps_edits_from_an_out_of_bounds_one_on() {
+        let script: EditScript = [
+            Substitute { c: 'a', index: 0 },
+            Delete { index: 5 },
+            Substitute { c: 'b', index: 1 },
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            script.minify("ab"),
+            [Delete { index: 5 }, Substitute { c: 'b', index: 1 }]
+                .into_iter()
+                .collect()
+        );
+    }
+
+    #[test]
+    fn byte_offsets_follow_evolving_string() {
+        let script: EditScript = [
+            Insert { c: 'ž', index: 0 },
+            Substitute { c: 'é', index: 2 },
+            Delete { index: 1 },
+        ]
+        .into_iter()
+        .collect();
+
+        let offsets: Vec<_> = script
+            .byte_offsets("abc")
+            .map(|(_, offset)| offset)
+            .collect();
+
+        assert_eq!(offsets, vec![0, 3, 2]);
+        assert_eq!(script.apply("abc"), "žéc");
+    }
+
+    #[test]
+    fn line_columns_account_for_newline_edits() {
+        let script: EditScript = [
+            Insert { c: '\n', index: 1 },
+            Substitute { c: 'x', index: 4 },
+            Delete { index: 1 },
+            Substitute { c: 'y', index: 3 },
+        ]
+        .into_iter()
+        .collect();
+
+        let positions: Vec<_> = script
+            .line_columns("ab\ncd")
+            .map(|(_, LineColumn { line, column })| (line, column))
+            .collect();
+
+        assert_eq!(positions, vec![(0, 1), (2, 0), (0, 1), (1, 0)]);
+        assert_eq!(script.apply("ab\ncd"), "ab\nyd");
+    }
+}
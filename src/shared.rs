@@ -0,0 +1,150 @@
+use std::{
+    sync::{Arc, Mutex, Weak},
+    thread,
+    time::Duration,
+};
+
+use crate::{MorphingString, Progress};
+
+/// A [`MorphingString`] advanced by a background thread at a fixed interval. Handles are cheap to
+/// clone and any thread can retarget the morph or read its latest frame.
+///
+/// The background thread stops once all the handles are dropped.
+///
+/// Frames are published behind a read-write lock, so reading one may briefly wait for a new frame
+/// to be published. Enable the optional `arc-swap` feature to publish them through a lock-free slot
+/// instead, so readers never block.
+#[derive(Clone)]
+pub struct SharedMorphingString {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    morph: Mutex<MorphingString>,
+    frame: FrameSlot,
+}
+
+/// Holds the latest frame. With the `arc-swap` feature readers never block, not even while a new
+/// frame is being published. Otherwise a read-write lock is used.
+struct FrameSlot {
+    #[cfg(feature = "arc-swap")]
+    frame: arc_swap::ArcSwap<String>,
+    #[cfg(not(feature = "arc-swap"))]
+    frame: std::sync::RwLock<Arc<String>>,
+}
+
+impl FrameSlot {
+    fn new(frame: String) -> Self {
+        Self {
+            #[cfg(feature = "arc-swap")]
+            frame: arc_swap::ArcSwap::from_pointee(frame),
+            #[cfg(not(feature = "arc-swap"))]
+            frame: std::sync::RwLock::new(Arc::new(frame)),
+        }
+    }
+
+    fn load(&self) -> Arc<String> {
+        #[cfg(feature = "arc-swap")]
+        return self.frame.load_full();
+        #[cfg(not(feature = "arc-swap"))]
+        return self
+            .frame
+            .read()
+            .expect("frame lock isn't poisoned")
+            .clone();
+    }
+
+    fn store(&self, frame: String) {
+        #[cfg(feature = "arc-swap")]
+        self.frame.store(Arc::new(frame));
+        #[cfg(not(feature = "arc-swap"))]
+        {
+            *self.frame.write().expect("frame lock isn't poisoned") = Arc::new(frame);
+        }
+    }
+}
+
+impl SharedMorphingString {
+    /// Share `morph` and advance it every `interval`, stretched by its
+    /// [`MorphingString::next_step_duration`], on a new background thread.
+    pub fn new(morph: MorphingString, interval: Duration) -> Self {
+        let inner = Arc::new(Inner {
+            frame: FrameSlot::new(morph.value().to_string()),
+            morph: Mutex::new(morph),
+        });
+
+        let weak = Arc::downgrade(&inner);
+        thread::spawn(move || tick(weak, interval));
+
+        Self { inner }
+    }
+
+    pub fn set_target(&self, target: String) {
+        self.inner
+            .morph
+            .lock()
+            .expect("morph lock isn't poisoned")
+            .set_target(target);
+    }
+
+    /// The latest frame. Only bumps a reference count and never waits for the morph to advance.
+    pub fn frame(&self) -> Arc<String> {
+        self.inner.frame.load()
+    }
+
+    pub fn progress(&self) -> Progress {
+        self.inner
+            .morph
+            .lock()
+            .expect("morph lock isn't poisoned")
+            .progress()
+    }
+}
+
+fn tick(inner: Weak<Inner>, interval: Duration) {
+    let mut wait = interval;
+    loop {
+        thread::sleep(wait);
+        let Some(inner) = inner.upgrade() else {
+            return;
+        };
+
+        let mut morph = inner.morph.lock().expect("morph lock isn't poisoned");
+        if morph.progress().is_complete() {
+            wait = interval;
+            continue;
+        }
+        morph.advance();
+        wait = morph.next_step_duration(interval);
+        let frame = morph.value().to_string();
+        drop(morph);
+
+        inner.frame.store(frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn background_thread_morphs_to_target() {
+        let shared = SharedMorphingString::new(
+            MorphingString::new("kitten".to_string()),
+            Duration::from_millis(1),
+        );
+        let setter = shared.clone();
+        thread::spawn(move || setter.set_target("mittens".to_string()))
+            .join()
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while *shared.frame() != "mittens" {
+            assert!(Instant::now() < deadline, "morph didn't finish in time");
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(shared.progress().is_complete());
+    }
+}
@@ -0,0 +1,77 @@
+//! Conversion of diffs computed by the `similar` crate into [`EditScript`]s and back.
+
+use ::similar::{ChangeTag, TextDiff};
+
+use crate::{
+    chunk::{self, Chunk},
+    script::EditScript,
+};
+
+impl<'old, 'new, 'bufs> From<&TextDiff<'old, 'new, 'bufs, str>> for EditScript {
+    fn from(diff: &TextDiff<'old, 'new, 'bufs, str>) -> Self {
+        diff.iter_all_changes()
+            .map(|change| (change.tag(), change.value()))
+            .collect()
+    }
+}
+
+/// Collect the changes of a diff, e.g. of a `similar::TextDiff` of any granularity, into the
+/// edits of the same morph.
+impl<S: AsRef<str>> FromIterator<(ChangeTag, S)> for EditScript {
+    fn from_iter<I: IntoIterator<Item = (ChangeTag, S)>>(changes: I) -> Self {
+        let mut chunks = Vec::new();
+        for (tag, text) in changes {
+            let text = text.as_ref().to_string();
+            let chunk = match tag {
+                ChangeTag::Equal => Chunk::Equal(text),
+                ChangeTag::Delete => Chunk::Delete(text),
+                ChangeTag::Insert => Chunk::Insert(text),
+            };
+            chunk::push(&mut chunks, chunk);
+        }
+        chunk::edits(&chunks).into()
+    }
+}
+
+impl EditScript {
+    /// The runs of chars the edits keep, delete and insert when applied to `start`, tagged the
+    /// way `similar` tags its changes.
+    pub fn to_changes(&self, start: &str) -> Vec<(ChangeTag, String)> {
+        chunk::chunks(start, self.iter())
+            .into_iter()
+            .map(|chunk| match chunk {
+                Chunk::Equal(text) => (ChangeTag::Equal, text),
+                Chunk::Delete(text) => (ChangeTag::Delete, text),
+                Chunk::Insert(text) => (ChangeTag::Insert, text),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levenshtein::compute_edit_sequence;
+
+    #[test]
+    fn text_diff_becomes_a_script() {
+        for (start, target) in [("kitten", "sitting"), ("", "abc"), ("abc", "")] {
+            let script = EditScript::from(&TextDiff::from_chars(start, target));
+
+            assert_eq!(script.apply(start), target);
+        }
+    }
+
+    #[test]
+    fn changes_round_trip() {
+        let script = EditScript::from(compute_edit_sequence("kitten", "sitting"));
+        let changes = script.to_changes("kitten");
+
+        assert_eq!(changes[0], (ChangeTag::Delete, "k".to_string()));
+        assert_eq!(changes[1], (ChangeTag::Insert, "s".to_string()));
+        assert_eq!(
+            changes.into_iter().collect::<EditScript>().apply("kitten"),
+            "sitting"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{FramePacer, MorphingText};
+
+/// A full-screen slideshow morphing between slides of multi-line text centered on the screen.
+///
+/// Runs on the alternate screen until the last slide was shown or the user quits with `q`, `Esc`
+/// or `Ctrl-C`, and restores the terminal afterwards, also on errors and panics.
+pub struct Slideshow<W: Write> {
+    out: W,
+    slides: Vec<String>,
+    dwell: Duration,
+    frame_interval: Duration,
+    looping: bool,
+}
+
+impl<W: Write> Slideshow<W> {
+    pub fn new(out: W, slides: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            out,
+            slides: slides.into_iter().map(Into::into).collect(),
+            dwell: Duration::from_secs(2),
+            frame_interval: Duration::from_millis(20),
+            looping: false,
+        }
+    }
+
+    /// Show every slide for `dwell` once it's fully morphed in. Two seconds by default.
+    pub fn with_dwell(mut self, dwell: Duration) -> Self {
+        self.dwell = dwell;
+        self
+    }
+
+    /// Show a frame of the transitions every `interval`. 20 milliseconds by default.
+    pub fn with_frame_interval(mut self, interval: Duration) -> Self {
+        self.frame_interval = interval;
+        self
+    }
+
+    /// Start over from the first slide after the last one until the user quits.
+    pub fn with_looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+
+    pub fn run(mut self) -> io::Result<()> {
+        let mut screen = Screen::enter(&mut self.out)?;
+        let mut text = MorphingText::new("");
+        let mut pacer = FramePacer::from_interval(self.frame_interval);
+        let shown = if self.looping {
+            usize::MAX
+        } else {
+            self.slides.len()
+        };
+
+        for slide in self.slides.iter().cycle().take(shown) {
+            text.set_target(slide);
+            loop {
+                screen.draw(&text)?;
+                if text.progress().is_complete() {
+                    break;
+                }
+                if !screen.wait(pacer.until_next_frame(), &text)? {
+                    return Ok(());
+                }
+                pacer.wait_for_next_frame();
+                text.advance();
+            }
+
+            let dwell_end = Instant::now() + self.dwell;
+            loop {
+                let remaining = dwell_end.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    break;
+                }
+                if !screen.wait(remaining, &text)? {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The alternate screen, left when dropped.
+struct Screen<'a, W: Write> {
+    out: &'a mut W,
+}
+
+impl<'a, W: Write> Screen<'a, W> {
+    fn enter(out: &'a mut W) -> io::Result<Self> {
+        // Raw mode turns Ctrl-C into a key event rather than a signal killing the process before
+        // the terminal is restored.
+        terminal::enable_raw_mode()?;
+        let screen = Self { out };
+        execute!(screen.out, EnterAlternateScreen, Hide)?;
+        Ok(screen)
+    }
+
+    fn draw(&mut self, text: &MorphingText) -> io::Result<()> {
+        let (columns, rows) = terminal::size()?;
+        let lines: Vec<&str> = text.lines().collect();
+        let top = usize::from(rows).saturating_sub(lines.len()) / 2;
+
+        queue!(self.out, Clear(ClearType::All))?;
+        for (row, line) in (top..).zip(lines) {
+            let column = usize::from(columns).saturating_sub(line.width()) / 2;
+            queue!(self.out, MoveTo(to_u16(column), to_u16(row)), Print(line))?;
+        }
+        self.out.flush()
+    }
+
+    /// Handle input for up to `timeout`, redrawing `text` on resizes. Returns false once the user
+    /// quits.
+    fn wait(&mut self, timeout: Duration, text: &MorphingText) -> io::Result<bool> {
+        let end = Instant::now() + timeout;
+        while event::poll(end.saturating_duration_since(Instant::now()))? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    let quit = match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => true,
+                        KeyCode::Char('c') => key.modifiers.contains(KeyModifiers::CONTROL),
+                        _ => false,
+                    };
+                    if quit {
+                        return Ok(false);
+                    }
+                }
+                Event::Resize(..) => self.draw(text)?,
+                _ => {}
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl<W: Write> Drop for Screen<'_, W> {
+    fn drop(&mut self) {
+        // Nothing to do about failures while restoring the terminal anyway.
+        let _ = execute!(self.out, LeaveAlternateScreen, Show);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn to_u16(n: usize) -> u16 {
+    n.try_into().unwrap_or(u16::MAX)
+}
@@ -0,0 +1,132 @@
+use crate::{edit::Edit, script::EditScript};
+
+/// Numbers of edits of every kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EditCounts {
+    pub inserts: usize,
+    pub deletes: usize,
+    pub substitutions: usize,
+}
+
+impl EditCounts {
+    pub fn total(&self) -> usize {
+        self.inserts + self.deletes + self.substitutions
+    }
+
+    pub(crate) fn add(&mut self, edit: &Edit) {
+        match edit {
+            Edit::Insert { .. } => self.inserts += 1,
+            Edit::Delete { .. } => self.deletes += 1,
+            Edit::Substitute { .. } => self.substitutions += 1,
+        }
+    }
+
+    pub(crate) fn remove(&mut self, edit: &Edit) {
+        match edit {
+            Edit::Insert { .. } => self.inserts -= 1,
+            Edit::Delete { .. } => self.deletes -= 1,
+            Edit::Substitute { .. } => self.substitutions -= 1,
+        }
+    }
+}
+
+impl FromIterator<Edit> for EditCounts {
+    fn from_iter<T: IntoIterator<Item = Edit>>(iter: T) -> Self {
+        let mut counts = Self::default();
+        for edit in iter {
+            counts.add(&edit);
+        }
+        counts
+    }
+}
+
+impl<'a> FromIterator<&'a Edit> for EditCounts {
+    fn from_iter<T: IntoIterator<Item = &'a Edit>>(iter: T) -> Self {
+        let mut counts = Self::default();
+        for edit in iter {
+            counts.add(edit);
+        }
+        counts
+    }
+}
+
+/// [`Progress`](crate::Progress) broken down by the kind of edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub total: EditCounts,
+    pub remaining: EditCounts,
+}
+
+/// Summary numbers of an [`EditScript`], see [`EditScript::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffStats {
+    /// The number of edits.
+    pub distance: usize,
+    pub counts: EditCounts,
+    /// The length of the target minus the length of the start, in chars.
+    pub length_delta: isize,
+    /// The most chars of the start that are kept next to each other untouched, in chars.
+    pub longest_unchanged_run: usize,
+}
+
+impl EditScript {
+    /// Summarize the edits morphing `start`, e.g. to decide whether to animate or snap.
+    pub fn stats(&self, start: &str) -> DiffStats {
+        let start_len = start.chars().count();
+        // The index in the start of every char, or None for touched ones.
+        let mut origins: Vec<Option<usize>> = (0..start_len).map(Some).collect();
+        for edit in self {
+            match edit {
+                Edit::Insert { index, .. } => origins.insert(index, None),
+                Edit::Delete { index } => {
+                    origins.remove(index);
+                }
+                Edit::Substitute { index, .. } => origins[index] = None,
+            }
+        }
+
+        let mut longest_unchanged_run = 0;
+        let mut run = 0;
+        let mut previous = None;
+        for origin in origins.iter().copied() {
+            run = match (previous, origin) {
+                (Some(previous), Some(origin)) if origin == previous + 1 => run + 1,
+                (_, Some(_)) => 1,
+                (_, None) => 0,
+            };
+            longest_unchanged_run = longest_unchanged_run.max(run);
+            previous = origin;
+        }
+
+        DiffStats {
+            distance: self.len(),
+            counts: self.iter().collect(),
+            length_delta: origins.len() as isize - start_len as isize,
+            longest_unchanged_run,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff;
+
+    #[test]
+    fn stats_summarize_the_script() {
+        let stats = diff("kitten", "sitting").stats("kitten");
+
+        assert_eq!(stats.distance, 3);
+        assert_eq!(stats.counts.substitutions, 2);
+        assert_eq!(stats.counts.inserts, 1);
+        assert_eq!(stats.length_delta, 1);
+        assert_eq!(stats.longest_unchanged_run, 3);
+    }
+
+    #[test]
+    fn runs_are_broken_by_deletions() {
+        let stats = diff("abXcd", "abcd").stats("abXcd");
+
+        assert_eq!(stats.longest_unchanged_run, 2);
+        assert_eq!(diff("", "").stats("").longest_unchanged_run, 0);
+    }
+}
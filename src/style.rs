@@ -0,0 +1,162 @@
+use crate::{Highlight, edit::Edit, render::Frame};
+
+/// How styled renderers style the chars of frames by how they change, with `S` being whatever
+/// the renderer styles text with, e.g. an [`anstyle::Style`] or the name of an HTML tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MorphStyle<S> {
+    pub inserted: S,
+    pub substituted: S,
+    /// Chars the next frame deletes.
+    pub doomed: S,
+    /// Chars that didn't change recently.
+    pub settled: S,
+    /// For how many frames inserted and substituted chars keep their style, counting the one they
+    /// changed in.
+    pub persist: usize,
+}
+
+impl<S> MorphStyle<S> {
+    pub fn new(inserted: S, substituted: S, doomed: S, settled: S) -> Self {
+        Self {
+            inserted,
+            substituted,
+            doomed,
+            settled,
+            persist: 1,
+        }
+    }
+
+    pub fn with_persist(mut self, frames: usize) -> Self {
+        self.persist = frames;
+        self
+    }
+
+    pub fn get(&self, highlight: Option<Highlight>) -> &S {
+        match highlight {
+            Some(Highlight::Inserted) => &self.inserted,
+            Some(Highlight::Substituted) => &self.substituted,
+            Some(Highlight::Deleting) => &self.doomed,
+            None => &self.settled,
+        }
+    }
+}
+
+#[cfg(feature = "anstyle")]
+impl Default for MorphStyle<anstyle::Style> {
+    fn default() -> Self {
+        use anstyle::{AnsiColor, Style};
+
+        Self::new(
+            Style::new().fg_color(Some(AnsiColor::Green.into())),
+            Style::new().fg_color(Some(AnsiColor::Yellow.into())),
+            Style::new().fg_color(Some(AnsiColor::Red.into())),
+            Style::new(),
+        )
+    }
+}
+
+/// Keeps track of how long ago the chars of successive frames changed, to keep them highlighted
+/// for [`MorphStyle::persist`] frames.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Fade {
+    // How every char of the last frame was changed and in how many frames it's been shown since.
+    chars: Vec<Option<(Highlight, usize)>>,
+}
+
+impl Fade {
+    /// The highlights of `frame` extended with those of recent frames.
+    pub fn update(&mut self, frame: &Frame, persist: usize) -> Vec<Option<Highlight>> {
+        // Follow the chars of the previous frame to where the last edits moved them.
+        for edit in &frame.last_edits {
+            match *edit {
+                Edit::Insert { index, .. } => self.chars.insert(index.min(self.chars.len()), None),
+                Edit::Delete { index } if index < self.chars.len() => {
+                    self.chars.remove(index);
+                }
+                Edit::Delete { .. } | Edit::Substitute { .. } => {}
+            }
+        }
+        self.chars.resize(frame.highlights.len(), None);
+
+        self.chars
+            .iter_mut()
+            .zip(&frame.highlights)
+            .map(|(state, &highlight)| {
+                *state = match (highlight, *state) {
+                    (Some(Highlight::Deleting), state) | (None, state) => {
+                        state.map(|(highlight, shown)| (highlight, shown + 1))
+                    }
+                    (Some(highlight), _) => Some((highlight, 1)),
+                };
+                match (highlight, *state) {
+                    (Some(Highlight::Deleting), _) => Some(Highlight::Deleting),
+                    (_, Some((highlight, shown))) if shown <= persist => Some(highlight),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Split `value` into runs of chars with the same highlight.
+pub(crate) fn runs<'a>(
+    value: &'a str,
+    highlights: &'a [Option<Highlight>],
+) -> impl Iterator<Item = (Option<Highlight>, &'a str)> {
+    let mut chars = value.char_indices().zip(highlights).peekable();
+    std::iter::from_fn(move || {
+        let ((start, _), &highlight) = chars.next()?;
+        let mut end = value.len();
+        while let Some(&((offset, _), &next)) = chars.peek() {
+            if next != highlight {
+                end = offset;
+                break;
+            }
+            chars.next();
+        }
+        Some((highlight, &value[start..end]))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MorphingString;
+
+    #[test]
+    fn highlights_persist_and_follow_their_chars() {
+        let mut morph = MorphingString::new("ab".to_string());
+        morph.set_target("xaby".to_string());
+        let mut fade = Fade::default();
+
+        let mut frames = Vec::new();
+        while !morph.advance().is_complete() {
+            frames.push(fade.update(&Frame::of(&morph), 2));
+        }
+        frames.push(fade.update(&Frame::of(&morph), 2));
+
+        use Highlight::Inserted;
+        assert_eq!(
+            frames,
+            [
+                vec![Some(Inserted), None, None],
+                vec![Some(Inserted), None, None, Some(Inserted)],
+            ]
+        );
+    }
+
+    #[test]
+    fn runs_group_equal_highlights() {
+        let highlights = [
+            None,
+            Some(Highlight::Inserted),
+            Some(Highlight::Inserted),
+            None,
+        ];
+
+        assert_eq!(
+            runs("ačbc", &highlights).collect::<Vec<_>>(),
+            [(None, "a"), (Some(Highlight::Inserted), "čb"), (None, "c")]
+        );
+    }
+}
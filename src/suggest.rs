@@ -0,0 +1,76 @@
+use crate::{EditScript, diff, levenshtein::distance_within};
+
+/// A dictionary entry close to a query, as found by [`suggest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Suggestion<'a> {
+    pub entry: &'a str,
+    pub distance: usize,
+}
+
+impl Suggestion<'_> {
+    /// The edits turning `query` into this suggestion, e.g. for animating the correction.
+    pub fn edit_script(&self, query: &str) -> EditScript {
+        diff(query, self.entry)
+    }
+}
+
+/// Find up to `limit` entries of `dictionary` that are at most `max_distance` edits away from
+/// `query`, nearest first. Entries at the same distance keep their dictionary order.
+pub fn suggest<'a, S: AsRef<str>>(
+    query: &str,
+    dictionary: &'a [S],
+    limit: usize,
+    max_distance: usize,
+) -> Vec<Suggestion<'a>> {
+    let mut suggestions: Vec<_> = dictionary
+        .iter()
+        .filter_map(|entry| {
+            let entry = entry.as_ref();
+            distance_within(query, entry, max_distance)
+                .map(|distance| Suggestion { entry, distance })
+        })
+        .collect();
+
+    suggestions.sort_by_key(|suggestion| suggestion.distance);
+    suggestions.truncate(limit);
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DICTIONARY: &[&str] = &["commit", "checkout", "cherry-pick", "clone", "config"];
+
+    #[test]
+    fn suggestions_are_nearest_first_within_threshold() {
+        let suggestions = suggest("comit", DICTIONARY, 3, 3);
+
+        assert_eq!(
+            suggestions,
+            vec![
+                Suggestion {
+                    entry: "commit",
+                    distance: 1,
+                },
+                Suggestion {
+                    entry: "config",
+                    distance: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn suggestions_are_limited() {
+        assert_eq!(suggest("comit", DICTIONARY, 1, 3).len(), 1);
+        assert!(suggest("comit", DICTIONARY, 0, 3).is_empty());
+    }
+
+    #[test]
+    fn edit_script_animates_the_correction() {
+        let suggestion = suggest("comit", DICTIONARY, 1, 2)[0];
+
+        assert_eq!(suggestion.edit_script("comit").apply("comit"), "commit");
+    }
+}
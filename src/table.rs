@@ -0,0 +1,178 @@
+use unicode_width::UnicodeWidthStr;
+
+use crate::{MorphingString, Progress};
+
+/// How cells of a [`MorphingTable`] column are aligned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// A grid of morphing cells rendered as rows aligned into columns. Columns are as wide as their
+/// widest cell in every frame, so they stay aligned while cells grow and shrink.
+pub struct MorphingTable {
+    cells: Vec<Vec<MorphingString>>,
+    alignments: Vec<Alignment>,
+    separator: String,
+}
+
+impl MorphingTable {
+    pub fn new(rows: Vec<Vec<String>>) -> Self {
+        Self {
+            cells: rows
+                .into_iter()
+                .map(|row| row.into_iter().map(MorphingString::new).collect())
+                .collect(),
+            alignments: Vec::new(),
+            separator: "  ".to_string(),
+        }
+    }
+
+    /// Align the columns in order. Columns without an alignment are aligned left.
+    pub fn with_alignments(mut self, alignments: Vec<Alignment>) -> Self {
+        self.alignments = alignments;
+        self
+    }
+
+    /// Separate columns with `separator`, two spaces by default.
+    pub fn with_separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Morph the cell at `row` and `column` towards `target`, adding empty cells up to it first if
+    /// the table is smaller.
+    pub fn set_cell(&mut self, row: usize, column: usize, target: String) {
+        if self.cells.len() <= row {
+            self.cells.resize_with(row + 1, Vec::new);
+        }
+        let cells = &mut self.cells[row];
+        if cells.len() <= column {
+            cells.resize_with(column + 1, || MorphingString::new(String::new()));
+        }
+        cells[column].set_target(target);
+    }
+
+    /// Morph every cell towards the cell of `rows` at the same position. Cells missing from `rows`
+    /// morph into empty ones.
+    pub fn set_rows(&mut self, rows: Vec<Vec<String>>) {
+        for (row, targets) in rows.iter().enumerate() {
+            for (column, target) in targets.iter().enumerate() {
+                self.set_cell(row, column, target.clone());
+            }
+        }
+        for (row, cells) in self.cells.iter_mut().enumerate() {
+            let targets = rows.get(row).map_or(0, Vec::len);
+            for cell in cells.iter_mut().skip(targets) {
+                cell.set_target(String::new());
+            }
+        }
+    }
+
+    /// Advance every cell by one step.
+    pub fn advance(&mut self) -> Progress {
+        for cell in self.cells.iter_mut().flatten() {
+            cell.advance();
+        }
+        self.progress()
+    }
+
+    /// Combined progress of all the cells.
+    pub fn progress(&self) -> Progress {
+        self.cells
+            .iter()
+            .flatten()
+            .map(MorphingString::progress)
+            .fold(
+                Progress {
+                    total_edits: 0,
+                    remaining_edits: 0,
+                },
+                |sum, progress| Progress {
+                    total_edits: sum.total_edits + progress.total_edits,
+                    remaining_edits: sum.remaining_edits + progress.remaining_edits,
+                },
+            )
+    }
+
+    /// The current, possibly intermediate, rows with the cells padded to the widths of their
+    /// columns, measured in terminal columns.
+    pub fn rows(&self) -> Vec<String> {
+        let mut widths: Vec<usize> = Vec::new();
+        for row in &self.cells {
+            for (column, cell) in row.iter().enumerate() {
+                if widths.len() <= column {
+                    widths.push(0);
+                }
+                widths[column] = widths[column].max(cell.value().width());
+            }
+        }
+
+        self.cells
+            .iter()
+            .map(|row| {
+                let cells: Vec<String> = widths
+                    .iter()
+                    .enumerate()
+                    .map(|(column, &width)| {
+                        let value = row.get(column).map_or("", MorphingString::value);
+                        let alignment = self.alignments.get(column).copied().unwrap_or_default();
+                        pad(value, width, alignment)
+                    })
+                    .collect();
+                cells.join(&self.separator)
+            })
+            .collect()
+    }
+}
+
+fn pad(value: &str, width: usize, alignment: Alignment) -> String {
+    let padding = width.saturating_sub(value.width());
+    let (left, right) = match alignment {
+        Alignment::Left => (0, padding),
+        Alignment::Right => (padding, 0),
+        Alignment::Center => (padding / 2, padding - padding / 2),
+    };
+    format!("{}{value}{}", " ".repeat(left), " ".repeat(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(cells: &[&[&str]]) -> Vec<Vec<String>> {
+        cells
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn columns_stay_aligned_while_morphing() {
+        let mut table = MorphingTable::new(rows(&[&["cpu", "9%"], &["memory", "120 MB"]]))
+            .with_alignments(vec![Alignment::Left, Alignment::Right]);
+        assert_eq!(table.rows(), vec!["cpu         9%", "memory  120 MB"]);
+
+        table.set_rows(rows(&[&["cpu", "10%"], &["mem", "1 MB"]]));
+        while !table.advance().is_complete() {
+            let widths: Vec<usize> = table.rows().iter().map(|row| row.width()).collect();
+            assert!(
+                widths.windows(2).all(|pair| pair[0] == pair[1]),
+                "{widths:?}"
+            );
+        }
+        assert_eq!(table.rows(), vec!["cpu   10%", "mem  1 MB"]);
+    }
+
+    #[test]
+    fn cells_are_added_as_needed() {
+        let mut table = MorphingTable::new(Vec::new()).with_separator("|");
+        table.set_cell(1, 1, "x".to_string());
+        while !table.advance().is_complete() {}
+
+        assert_eq!(table.rows(), vec!["| ", "|x"]);
+    }
+}
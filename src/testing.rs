@@ -0,0 +1,102 @@
+//! Helpers for pinning animation output in snapshot tests.
+
+use crate::MorphingString;
+
+/// Every frame of the morph from `start` to `target`, both included, by a [`MorphingString`]
+/// configured with `config`. The random number generator is seeded with 0 unless `config` seeds
+/// it differently, so the frames are the same on every run.
+pub fn collect_frames(
+    start: &str,
+    target: &str,
+    config: impl FnOnce(MorphingString) -> MorphingString,
+) -> Vec<String> {
+    let mut morph = config(MorphingString::new(start.to_string()).with_seed(0));
+    morph.set_target(target.to_string());
+
+    let mut frames = vec![morph.value().to_string()];
+    while !morph.advance().is_complete() {
+        frames.push(morph.value().to_string());
+    }
+    if frames.last().map(String::as_str) != Some(morph.value()) {
+        frames.push(morph.value().to_string());
+    }
+    frames
+}
+
+/// Encode `frames` one per line between `|`s, which keep leading and trailing whitespace visible.
+/// Backslashes, line breaks, tabs and other control chars are escaped.
+pub fn encode_frames(frames: &[impl AsRef<str>]) -> String {
+    let mut encoded = String::new();
+    for frame in frames {
+        encoded.push('|');
+        for c in frame.as_ref().chars() {
+            match c {
+                '\\' => encoded.push_str("\\\\"),
+                '\n' => encoded.push_str("\\n"),
+                '\r' => encoded.push_str("\\r"),
+                '\t' => encoded.push_str("\\t"),
+                c if c.is_control() => encoded.push_str(&format!("\\u{{{:x}}}", c as u32)),
+                c => encoded.push(c),
+            }
+        }
+        encoded.push_str("|\n");
+    }
+    encoded
+}
+
+/// Decode frames encoded by [`encode_frames`]. Returns `None` if `encoded` isn't valid.
+pub fn decode_frames(encoded: &str) -> Option<Vec<String>> {
+    encoded.lines().map(decode_frame).collect()
+}
+
+fn decode_frame(line: &str) -> Option<String> {
+    let mut chars = line.strip_prefix('|')?.strip_suffix('|')?.chars();
+    let mut frame = String::new();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            frame.push(c);
+            continue;
+        }
+        frame.push(match chars.next()? {
+            '\\' => '\\',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let rest = chars.as_str().strip_prefix('{')?;
+                let (hex, rest) = rest.split_once('}')?;
+                chars = rest.chars();
+                char::from_u32(u32::from_str_radix(hex, 16).ok()?)?
+            }
+            _ => return None,
+        });
+    }
+    Some(frame)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Granularity;
+
+    #[test]
+    fn frames_are_collected_with_the_config() {
+        assert_eq!(
+            collect_frames("ab cd", "xy zw", |morph| morph
+                .with_granularity(Granularity::Word)),
+            vec!["ab cd", "xy cd", "xy zw"]
+        );
+        assert_eq!(collect_frames("ab", "ab", |morph| morph), vec!["ab"]);
+    }
+
+    #[test]
+    fn encoding_round_trips() {
+        let frames = [" a\\b ", "\n\t\r", "\u{1b}[0m", ""];
+        let encoded = encode_frames(&frames);
+
+        assert_eq!(encoded, "| a\\\\b |\n|\\n\\t\\r|\n|\\u{1b}[0m|\n||\n");
+        assert_eq!(decode_frames(&encoded).unwrap(), frames);
+        assert_eq!(decode_frames("|a\\x|"), None);
+        assert_eq!(decode_frames("a"), None);
+    }
+}
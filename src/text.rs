@@ -0,0 +1,94 @@
+use crate::{edit::Edit, script::EditScript};
+
+/// A text buffer [`Edit`]s can be applied to in place. Indexes are char indexes.
+pub trait EditableText {
+    fn insert_char(&mut self, index: usize, c: char);
+    fn remove_char(&mut self, index: usize);
+
+    fn replace_char(&mut self, index: usize, c: char) {
+        self.remove_char(index);
+        self.insert_char(index, c);
+    }
+}
+
+impl EditableText for String {
+    fn insert_char(&mut self, index: usize, c: char) {
+        Edit::Insert { c, index }.apply_in_place(self);
+    }
+
+    fn remove_char(&mut self, index: usize) {
+        Edit::Delete { index }.apply_in_place(self);
+    }
+
+    fn replace_char(&mut self, index: usize, c: char) {
+        Edit::Substitute { c, index }.apply_in_place(self);
+    }
+}
+
+#[cfg(feature = "ropey")]
+impl EditableText for ropey::Rope {
+    fn insert_char(&mut self, index: usize, c: char) {
+        ropey::Rope::insert_char(self, index, c);
+    }
+
+    fn remove_char(&mut self, index: usize) {
+        self.remove(index..index + 1);
+    }
+}
+
+impl Edit {
+    /// Apply the edit onto any [`EditableText`].
+    pub fn apply_to(&self, text: &mut impl EditableText) {
+        match *self {
+            Edit::Insert { c, index } => text.insert_char(index, c),
+            Edit::Delete { index } => text.remove_char(index),
+            Edit::Substitute { c, index } => text.replace_char(index, c),
+        }
+    }
+}
+
+impl EditScript {
+    /// Apply all the edits, front to back, onto any [`EditableText`].
+    pub fn apply_to(&self, text: &mut impl EditableText) {
+        for edit in self {
+            edit.apply_to(text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff;
+
+    /// A deliberately naive buffer relying on the default `replace_char`.
+    struct Chars(Vec<char>);
+
+    impl EditableText for Chars {
+        fn insert_char(&mut self, index: usize, c: char) {
+            self.0.insert(index, c);
+        }
+
+        fn remove_char(&mut self, index: usize) {
+            self.0.remove(index);
+        }
+    }
+
+    #[test]
+    fn script_applies_to_string_in_place() {
+        let mut text = "sunday".to_string();
+
+        diff("sunday", "sätürday").apply_to(&mut text);
+
+        assert_eq!(text, "sätürday");
+    }
+
+    #[test]
+    fn script_applies_to_custom_buffer() {
+        let mut text = Chars("kitten".chars().collect());
+
+        diff("kitten", "mittens").apply_to(&mut text);
+
+        assert_eq!(String::from_iter(text.0), "mittens");
+    }
+}
@@ -0,0 +1,15 @@
+/// Which kind of move the edit distance backtrace takes when several lead to scripts with the
+/// lowest cost. Moves are taken from the ends of the strings backwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// Take whichever move keeps the part of the strings given by the [`Anchor`](crate::Anchor)
+    /// aligned.
+    #[default]
+    Anchored,
+    /// Substitute or keep chars, then insert, then delete.
+    PreferSubstitutions,
+    /// Delete, then substitute or keep chars, then insert, shrinking the value early.
+    PreferDeletions,
+    /// Insert, then substitute or keep chars, then delete, growing the value early.
+    PreferInsertions,
+}
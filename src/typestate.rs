@@ -0,0 +1,153 @@
+//! A typestate variant of [`MorphingString`] where only a morph in progress can be advanced.
+//!
+//! ```
+//! use morphing_string::typestate::{Morph, State};
+//!
+//! let mut state = Morph::new("kitten".to_string()).morph_to("sitting".to_string());
+//! while let State::Active(active) = state {
+//!     println!("{}", active.value());
+//!     state = active.advance();
+//! }
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::{MorphingString, Progress};
+
+/// The state of a [`Morph`] that has reached its target.
+#[derive(Debug, Clone, Copy)]
+pub struct Idle;
+
+/// The state of a [`Morph`] with edits left to apply.
+#[derive(Debug, Clone, Copy)]
+pub struct Active;
+
+pub struct Morph<S> {
+    inner: MorphingString,
+    state: PhantomData<S>,
+}
+
+/// A [`Morph`] in whichever state it turned out to be in.
+pub enum State {
+    Idle(Morph<Idle>),
+    Active(Morph<Active>),
+}
+
+impl<S> Morph<S> {
+    pub fn value(&self) -> &str {
+        self.inner.value()
+    }
+
+    pub fn progress(&self) -> Progress {
+        self.inner.progress()
+    }
+
+    /// The dynamic [`MorphingString`] in the same state.
+    pub fn into_inner(self) -> MorphingString {
+        self.inner
+    }
+
+    fn with_state<T>(self) -> Morph<T> {
+        Morph {
+            inner: self.inner,
+            state: PhantomData,
+        }
+    }
+}
+
+impl Morph<Idle> {
+    pub fn new(value: String) -> Self {
+        Self {
+            inner: MorphingString::new(value),
+            state: PhantomData,
+        }
+    }
+
+    /// Start morphing towards `target`. Stays idle if there's nothing to change.
+    pub fn morph_to(mut self, target: String) -> State {
+        self.inner.set_target(target);
+        State::from(self.inner)
+    }
+}
+
+impl Morph<Active> {
+    /// Apply the next step of edits, becoming idle once the target is reached.
+    pub fn advance(mut self) -> State {
+        self.inner.advance();
+        State::from(self.inner)
+    }
+
+    /// Morph towards `target` according to the configured
+    /// [`RetargetPolicy`](crate::RetargetPolicy) instead.
+    pub fn retarget(mut self, target: String) -> State {
+        self.inner.set_target(target);
+        State::from(self.inner)
+    }
+
+    /// Jump straight to the target.
+    pub fn finish(mut self) -> Morph<Idle> {
+        while !self.inner.advance().is_complete() {}
+        self.with_state()
+    }
+}
+
+/// Wraps a configured [`MorphingString`], which may be in the middle of a morph.
+impl From<MorphingString> for State {
+    fn from(inner: MorphingString) -> Self {
+        if inner.progress().is_complete() {
+            State::Idle(Morph {
+                inner,
+                state: PhantomData,
+            })
+        } else {
+            State::Active(Morph {
+                inner,
+                state: PhantomData,
+            })
+        }
+    }
+}
+
+impl State {
+    pub fn value(&self) -> &str {
+        match self {
+            State::Idle(morph) => morph.value(),
+            State::Active(morph) => morph.value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advancing_ends_idle_at_the_target() {
+        let mut state = Morph::new("abc".to_string()).morph_to("abd".to_string());
+        let mut frames = 0;
+        let idle = loop {
+            match state {
+                State::Active(active) => {
+                    frames += 1;
+                    state = active.advance();
+                }
+                State::Idle(idle) => break idle,
+            }
+        };
+
+        assert_eq!(frames, 1);
+        assert_eq!(idle.value(), "abd");
+        assert!(matches!(idle.morph_to("abd".to_string()), State::Idle(_)));
+    }
+
+    #[test]
+    fn configured_morphs_keep_their_state() {
+        let mut inner = MorphingString::new("abc".to_string()).with_seed(1);
+        inner.set_target("xyz".to_string());
+
+        let State::Active(active) = State::from(inner) else {
+            panic!("the morph is in progress");
+        };
+        assert_eq!(active.finish().value(), "xyz");
+    }
+}
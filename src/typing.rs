@@ -0,0 +1,167 @@
+use std::{collections::VecDeque, time::Duration};
+
+use crate::{edit::Edit, rng::Rng};
+
+// The letter rows of a QWERTY keyboard, for typos hitting a neighbouring key.
+const ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// A simulation of a person typing, used by [`Effect::Typing`](crate::Effect::Typing).
+///
+/// The effect makes occasional typos that are corrected with a backspace. The timing of the
+/// keystrokes, in bursts of quick ones separated by pauses, comes from [`Typing::delays`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Typing {
+    typo_percent: usize,
+    interval: Duration,
+    jitter_percent: usize,
+    max_burst: usize,
+    pause: Duration,
+}
+
+impl Typing {
+    pub fn new() -> Self {
+        Self {
+            typo_percent: 5,
+            interval: Duration::from_millis(120),
+            jitter_percent: 50,
+            max_burst: 5,
+            pause: Duration::from_millis(400),
+        }
+    }
+
+    /// Mistype a neighbouring key for `percent` of the typed letters.
+    pub fn with_typo_percent(mut self, percent: usize) -> Self {
+        self.typo_percent = percent.min(100);
+        self
+    }
+
+    /// Wait `interval` between keystrokes on average, varying every wait by up to
+    /// `jitter_percent` of it.
+    pub fn with_interval(mut self, interval: Duration, jitter_percent: usize) -> Self {
+        self.interval = interval;
+        self.jitter_percent = jitter_percent.min(100);
+        self
+    }
+
+    /// Type in bursts of up to `max_burst` keystrokes, pausing for an extra `pause` after each.
+    pub fn with_bursts(mut self, max_burst: usize, pause: Duration) -> Self {
+        self.max_burst = max_burst.max(1);
+        self.pause = pause;
+        self
+    }
+
+    /// Turn the planned edits into keystrokes, inserting typos and their corrections.
+    pub(crate) fn apply(&self, edits: VecDeque<Edit>, rng: &mut Rng) -> VecDeque<Edit> {
+        let mut keystrokes = VecDeque::with_capacity(edits.len());
+        for edit in edits {
+            let (c, index) = match edit {
+                Edit::Insert { c, index } | Edit::Substitute { c, index } => (c, index),
+                Edit::Delete { .. } => {
+                    keystrokes.push_back(edit);
+                    continue;
+                }
+            };
+
+            let typo = neighbour(c, rng).filter(|_| rng.below(100) < self.typo_percent);
+            let Some(typo) = typo else {
+                keystrokes.push_back(edit);
+                continue;
+            };
+            keystrokes.push_back(match edit {
+                Edit::Insert { .. } => Edit::Insert { c: typo, index },
+                _ => Edit::Substitute { c: typo, index },
+            });
+            keystrokes.push_back(Edit::Delete { index });
+            keystrokes.push_back(Edit::Insert { c, index });
+        }
+        keystrokes
+    }
+
+    /// The waits before each of `keystrokes` keystrokes, e.g. frames of a morph with this effect,
+    /// drawn reproducibly from `seed`.
+    pub fn delays(&self, keystrokes: usize, seed: u64) -> Vec<Duration> {
+        let mut rng = Rng::new(seed);
+        let mut burst_left = 0;
+        (0..keystrokes)
+            .map(|_| {
+                let jitter = self.interval.mul_f64(self.jitter_percent as f64 / 100.0);
+                let offset = jitter.mul_f64(rng.below(201) as f64 / 100.0);
+                let mut delay = (self.interval + offset).saturating_sub(jitter);
+                if burst_left == 0 {
+                    burst_left = 1 + rng.below(self.max_burst);
+                    delay += self.pause;
+                }
+                burst_left -= 1;
+                delay
+            })
+            .collect()
+    }
+}
+
+impl Default for Typing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A random key next to the key of `c`, if it's a letter.
+fn neighbour(c: char, rng: &mut Rng) -> Option<char> {
+    let lower = c.to_ascii_lowercase();
+    let row = ROWS.iter().find(|row| row.contains(lower))?.as_bytes();
+    let column = row.iter().position(|&key| key as char == lower)?;
+    let neighbours: Vec<u8> = [column.checked_sub(1), Some(column + 1)]
+        .into_iter()
+        .flatten()
+        .filter_map(|column| row.get(column).copied())
+        .collect();
+    let key = neighbours[rng.below(neighbours.len())] as char;
+    Some(if c.is_ascii_uppercase() {
+        key.to_ascii_uppercase()
+    } else {
+        key
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levenshtein::compute_edit_sequence;
+
+    #[test]
+    fn typos_are_corrected_with_a_backspace() {
+        let typing = Typing::new().with_typo_percent(100);
+        let edits = compute_edit_sequence("", "hi!");
+
+        let keystrokes = typing.apply(edits, &mut Rng::new(1));
+
+        let mut frames = vec![String::new()];
+        for keystroke in &keystrokes {
+            frames.push(keystroke.apply(frames.last().expect("non-empty")));
+        }
+        assert_eq!(frames.last().expect("non-empty"), "hi!");
+        // Both letters are mistyped, the `!` isn't on a letter row.
+        assert_eq!(keystrokes.len(), 3 + 2 * 2);
+        assert!(frames[1] == "g" || frames[1] == "j", "{frames:?}");
+    }
+
+    #[test]
+    fn delays_are_reproducible_and_pause_between_bursts() {
+        let typing = Typing::new()
+            .with_interval(Duration::from_millis(100), 20)
+            .with_bursts(3, Duration::from_secs(1));
+
+        let delays = typing.delays(20, 7);
+
+        assert_eq!(delays, typing.delays(20, 7));
+        assert!(delays[0] >= Duration::from_secs(1));
+        let pauses = delays
+            .iter()
+            .filter(|delay| **delay >= Duration::from_secs(1));
+        assert!(pauses.count() >= 20 / 3);
+        assert!(
+            delays
+                .iter()
+                .all(|delay| delay.as_millis() % 1000 >= 80 && delay.as_millis() % 1000 <= 120)
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use tokio::sync::watch;
+
+use crate::{MorphingString, Progress};
+
+/// A [`MorphingString`] publishing every new frame into a [`tokio::sync::watch`] channel so any
+/// number of async consumers can observe the morph without polling it.
+pub struct WatchedMorphingString {
+    morph: MorphingString,
+    sender: watch::Sender<String>,
+}
+
+impl WatchedMorphingString {
+    pub fn new(morph: MorphingString) -> (Self, watch::Receiver<String>) {
+        let (sender, receiver) = watch::channel(morph.value().to_string());
+        (Self { morph, sender }, receiver)
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<String> {
+        self.sender.subscribe()
+    }
+
+    pub fn set_target(&mut self, target: String) {
+        self.morph.set_target(target);
+    }
+
+    /// Advance the morph and publish the new frame. Receivers are only notified if the frame
+    /// actually changed.
+    pub fn advance(&mut self) -> Progress {
+        let progress = self.morph.advance();
+        let value = self.morph.value();
+
+        self.sender.send_if_modified(|frame| {
+            if frame == value {
+                return false;
+            }
+            frame.clear();
+            frame.push_str(value);
+            true
+        });
+
+        progress
+    }
+
+    pub fn morph(&self) -> &MorphingString {
+        &self.morph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_are_published() {
+        let (mut watched, mut receiver) =
+            WatchedMorphingString::new(MorphingString::new("abc".to_string()));
+        let late = watched.subscribe();
+        watched.set_target("abd".to_string());
+
+        assert_eq!(*receiver.borrow_and_update(), "abc");
+        watched.advance();
+        assert!(receiver.has_changed().unwrap());
+        assert_eq!(*receiver.borrow_and_update(), "abd");
+
+        watched.advance();
+        assert!(!receiver.has_changed().unwrap());
+        assert_eq!(*late.borrow(), "abd");
+    }
+}
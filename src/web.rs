@@ -0,0 +1,193 @@
+//! Exporting timed frames for static web pages, so they can replay a morph without running this
+//! crate client-side. Frames with their durations come from [`Pacing::timed_frames`].
+//!
+//! [`Pacing::timed_frames`]: crate::Pacing::timed_frames
+
+use std::{fmt::Write, time::Duration};
+
+/// An HTML snippet of a `<span>` with the given `id` that plays `frames` once with CSS keyframes
+/// animating its `::after` content, then keeps showing the last frame.
+pub fn html_snippet(frames: &[(String, Duration)], id: &str) -> String {
+    let total: Duration = frames.iter().map(|(_, duration)| *duration).sum();
+    let last = frames.last().map_or("", |(frame, _)| frame.as_str());
+
+    let mut keyframes = String::new();
+    let mut shown_at = Duration::ZERO;
+    for (frame, duration) in frames {
+        let percent = if total.is_zero() {
+            0.0
+        } else {
+            100.0 * shown_at.as_secs_f64() / total.as_secs_f64()
+        };
+        let _ = writeln!(
+            keyframes,
+            "  {percent:.3}% {{ content: {}; }}",
+            css_string(frame)
+        );
+        shown_at += *duration;
+    }
+
+    let ident = css_ident(id);
+    format!(
+        "<style>\n\
+         @keyframes morph-{ident} {{\n{keyframes}}}\n\
+         #{ident}::after {{ content: {}; white-space: pre; animation: morph-{ident} {:.3}s forwards; }}\n\
+         </style>\n\
+         <span id=\"{}\" aria-label=\"{}\"></span>\n",
+        css_string(last),
+        total.as_secs_f64(),
+        html_escape(id),
+        html_escape(last),
+    )
+}
+
+/// A JSON array of `{"text": ..., "duration_ms": ...}` objects, one per frame, for replaying
+/// `frames` with a few lines of JavaScript.
+pub fn json(frames: &[(String, Duration)]) -> String {
+    let mut json = String::from("[");
+    for (i, (frame, duration)) in frames.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        let _ = write!(
+            json,
+            "{{\"text\":{},\"duration_ms\":{}}}",
+            json_string(frame),
+            duration.as_millis()
+        );
+    }
+    json.push(']');
+    json
+}
+
+/// `text` as a quoted CSS string. `<` is escaped too so a frame can't close the `<style>` element.
+fn css_string(text: &str) -> String {
+    let mut string = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' | '\\' => {
+                string.push('\\');
+                string.push(c);
+            }
+            c if c.is_control() || c == '<' => css_hex_escape(&mut string, c),
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+    string
+}
+
+/// `text` as a CSS identifier, escaped like `CSS.escape` does in browsers, plus `<` like in
+/// [`css_string`].
+fn css_ident(text: &str) -> String {
+    let mut ident = String::new();
+    let starts_with_hyphen = text.starts_with('-');
+    for (i, c) in text.chars().enumerate() {
+        let leading_digit = c.is_ascii_digit() && (i == 0 || (i == 1 && starts_with_hyphen));
+        match c {
+            '\0' => ident.push(char::REPLACEMENT_CHARACTER),
+            c if c.is_ascii_control() || c == '<' || leading_digit => {
+                css_hex_escape(&mut ident, c);
+            }
+            '-' if text.len() == 1 => ident.push_str("\\-"),
+            c if !c.is_ascii() || c.is_ascii_alphanumeric() || c == '-' || c == '_' => {
+                ident.push(c);
+            }
+            c => {
+                ident.push('\\');
+                ident.push(c);
+            }
+        }
+    }
+    ident
+}
+
+fn css_hex_escape(string: &mut String, c: char) {
+    // The space ends the escape in case a hex digit follows.
+    let _ = write!(string, "\\{:x} ", c as u32);
+}
+
+fn json_string(text: &str) -> String {
+    let mut string = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(string, "\\u{:04x}", c as u32);
+            }
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+    string
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames() -> Vec<(String, Duration)> {
+        vec![
+            ("ab".to_string(), Duration::from_millis(100)),
+            ("a\"".to_string(), Duration::from_millis(300)),
+            ("<a>\n".to_string(), Duration::ZERO),
+        ]
+    }
+
+    #[test]
+    fn snippet_animates_the_content() {
+        assert_eq!(
+            html_snippet(&frames(), "title"),
+            "<style>\n\
+             @keyframes morph-title {\n  \
+               0.000% { content: \"ab\"; }\n  \
+               25.000% { content: \"a\\\"\"; }\n  \
+               100.000% { content: \"\\3c a>\\a \"; }\n\
+             }\n\
+             #title::after { content: \"\\3c a>\\a \"; white-space: pre; \
+             animation: morph-title 0.400s forwards; }\n\
+             </style>\n\
+             <span id=\"title\" aria-label=\"&lt;a&gt;\n\"></span>\n"
+        );
+    }
+
+    #[test]
+    fn frames_and_ids_are_escaped_for_css() {
+        let frames = [("</style><b>".to_string(), Duration::from_millis(100))];
+
+        let snippet = html_snippet(&frames, "1 a.b{");
+
+        assert_eq!(snippet.matches("</style>").count(), 1);
+        assert!(snippet.contains("content: \"\\3c /style>\\3c b>\";"));
+        assert!(snippet.contains("@keyframes morph-\\31 \\ a\\.b\\{ {"));
+        assert!(snippet.contains("#\\31 \\ a\\.b\\{::after"));
+        assert!(snippet.contains("<span id=\"1 a.b{\""));
+    }
+
+    #[test]
+    fn ids_escape_like_css_escape() {
+        assert_eq!(css_ident("title-2"), "title-2");
+        assert_eq!(css_ident("-2x"), "-\\32 x");
+        assert_eq!(css_ident("-"), "\\-");
+        assert_eq!(css_ident("čau"), "čau");
+    }
+
+    #[test]
+    fn json_lists_frames() {
+        assert_eq!(
+            json(&frames()),
+            "[{\"text\":\"ab\",\"duration_ms\":100},\
+             {\"text\":\"a\\\"\",\"duration_ms\":300},\
+             {\"text\":\"<a>\\n\",\"duration_ms\":0}]"
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use std::collections::VecDeque;
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::{MorphingString, Progress, distance, measure::display_width};
+
+/// How a [`WrappedMorphingString`] transitions to a new target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Transition {
+    /// Morph every row into the corresponding row of the target.
+    #[default]
+    Morph,
+    /// First scroll the rows up, one row per step, with rows of the target sliding in from the
+    /// bottom, then morph the rows that still differ. Scrolls by however many rows makes the
+    /// transition shortest, so unrelated text scrolls out entirely while e.g. a log with a line
+    /// appended scrolls by one row.
+    Scroll,
+}
+
+/// A string soft-wrapped to a fixed width and morphed row by row.
+///
+/// Both the current value and every new target are wrapped to `width` display columns and each row
+/// morphs towards the corresponding row of the target. Rows morph with a monotonic length and a
+/// maximum width (see [`MorphingString::with_monotonic_length`] and
+/// [`MorphingString::with_max_width`]), so no row ever grows past `width` columns mid-morph.
+pub struct WrappedMorphingString {
+    width: usize,
+    rows: Vec<MorphingString>,
+    target_rows: usize,
+    // Rows yet to scroll in from the bottom before the rows morph into `scroll_target`.
+    scroll: VecDeque<String>,
+    scroll_target: Vec<String>,
+}
+
+impl WrappedMorphingString {
+    pub fn new(value: &str, width: usize) -> Self {
+        let rows: Vec<_> = wrap(value, width)
+            .into_iter()
+            .map(|row| row_morph(row, width))
+            .collect();
+
+        Self {
+            width,
+            target_rows: rows.len(),
+            rows,
+            scroll: VecDeque::new(),
+            scroll_target: Vec::new(),
+        }
+    }
+
+    pub fn set_target(&mut self, target: &str) {
+        self.set_target_with(target, Transition::Morph);
+    }
+
+    pub fn set_target_with(&mut self, target: &str, transition: Transition) {
+        let target_rows = wrap(target, self.width);
+        self.scroll.clear();
+
+        if transition == Transition::Scroll {
+            let current: Vec<&str> = self.rows().collect();
+            let scroll = (0..=current.len())
+                .min_by_key(|&scroll| scroll_cost(&current, &target_rows, scroll))
+                .expect("non-empty range");
+
+            if scroll > 0 {
+                let incoming = current.len() - scroll;
+                self.scroll = (incoming..current.len())
+                    .map(|i| target_rows.get(i).cloned().unwrap_or_default())
+                    .collect();
+                self.scroll_target = target_rows;
+                // Stop the rows still morphing towards the previous target where they are.
+                for row in &mut self.rows {
+                    row.set_target(row.value().to_string());
+                }
+                return;
+            }
+        }
+
+        self.morph_rows(target_rows);
+    }
+
+    fn morph_rows(&mut self, target_rows: Vec<String>) {
+        while self.rows.len() < target_rows.len() {
+            self.rows.push(row_morph(String::new(), self.width));
+        }
+
+        let mut target_rows = target_rows.into_iter();
+        self.target_rows = target_rows.len();
+        for row in &mut self.rows {
+            row.set_target(target_rows.next().unwrap_or_default());
+        }
+    }
+
+    /// Scroll by one row or, when done scrolling, advance every row by one step.
+    pub fn advance(&mut self) -> Progress {
+        if let Some(incoming) = self.scroll.pop_front() {
+            self.rows.remove(0);
+            self.rows.push(row_morph(incoming, self.width));
+            if self.scroll.is_empty() {
+                let target_rows = std::mem::take(&mut self.scroll_target);
+                self.morph_rows(target_rows);
+            }
+        } else {
+            for row in &mut self.rows {
+                row.advance();
+            }
+        }
+
+        let progress = self.progress();
+        if progress.is_complete() {
+            // Rows that morphed into nothing aren't part of the target.
+            self.rows.truncate(self.target_rows);
+        }
+        progress
+    }
+
+    /// Combined progress of all the rows. Pending scroll steps count as edits, and the edits of
+    /// the morph that follows a scroll are only known once the scrolling is done.
+    pub fn progress(&self) -> Progress {
+        self.rows.iter().map(MorphingString::progress).fold(
+            Progress {
+                total_edits: self.scroll.len(),
+                remaining_edits: self.scroll.len(),
+            },
+            |sum, progress| Progress {
+                total_edits: sum.total_edits + progress.total_edits,
+                remaining_edits: sum.remaining_edits + progress.remaining_edits,
+            },
+        )
+    }
+
+    /// The current, possibly intermediate, rows. None of them is wider than the width.
+    pub fn rows(&self) -> impl Iterator<Item = &str> {
+        self.rows.iter().map(MorphingString::value)
+    }
+}
+
+fn row_morph(row: String, width: usize) -> MorphingString {
+    MorphingString::new(row)
+        .with_monotonic_length()
+        .with_max_width(width)
+}
+
+/// The number of steps it takes to scroll `current` up by `scroll` rows and then morph the rows
+/// into `target`.
+fn scroll_cost(current: &[&str], target: &[String], scroll: usize) -> usize {
+    let staying = current.len() - scroll;
+    let morph: usize = (0..staying)
+        .map(|i| {
+            distance(
+                current[scroll + i],
+                target.get(i).map_or("", String::as_str),
+            )
+        })
+        .sum();
+    // Rows scrolling in are already right. Rows the target has beyond the current height have
+    // to be typed in either way.
+    let appended: usize = target
+        .iter()
+        .skip(current.len())
+        .map(|row| row.chars().count())
+        .sum();
+
+    scroll + morph + appended
+}
+
+/// Greedily wrap `text` into rows of at most `width` display columns, breaking at whitespace where
+/// possible and always at newlines. Words wider than `width` are split.
+pub(crate) fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+
+    for line in text.split('\n') {
+        let mut row = String::new();
+        let mut row_width = 0;
+        // The whitespace that followed the last word, kept if the next word fits on the same row.
+        let mut separator = None;
+
+        for token in line.split_inclusive(char::is_whitespace) {
+            let (mut word, next_separator) = match token.chars().next_back() {
+                Some(c) if c.is_whitespace() => (&token[..token.len() - c.len_utf8()], Some(c)),
+                _ => (token, None),
+            };
+            let mut word_width = display_width(word);
+            let separator_width = separator.map_or(0, |c: char| c.width().unwrap_or(0));
+
+            if !row.is_empty() && row_width + separator_width + word_width > width {
+                rows.push(std::mem::take(&mut row));
+                row_width = 0;
+            } else if !row.is_empty()
+                && let Some(separator) = separator
+            {
+                row.push(separator);
+                row_width += separator_width;
+            }
+
+            while word_width > width {
+                let (split, split_width) = split_at_width(word, width);
+                if split == word.len() {
+                    // A single char wider than the row.
+                    break;
+                }
+                rows.push(word[..split].to_string());
+                word = &word[split..];
+                word_width -= split_width;
+            }
+
+            row.push_str(word);
+            row_width += word_width;
+            separator = next_separator;
+        }
+
+        rows.push(row);
+    }
+
+    rows
+}
+
+/// The byte offset and width of the longest non-empty prefix of `word` at most `width` columns
+/// wide, or of its first char if that alone is wider.
+fn split_at_width(word: &str, width: usize) -> (usize, usize) {
+    let mut split = (0, 0);
+    for (offset, c) in word.char_indices() {
+        let end = (offset + c.len_utf8(), split.1 + c.width().unwrap_or(0));
+        if end.1 > width && split.0 > 0 {
+            break;
+        }
+        split = end;
+    }
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_at_whitespace_and_splits_long_words() {
+        assert_eq!(
+            wrap("the quick brown fox", 10),
+            vec!["the quick", "brown fox"]
+        );
+        assert_eq!(wrap("abcdefghij klm", 4), vec!["abcd", "efgh", "ij", "klm"]);
+        assert_eq!(wrap("one\ntwo", 10), vec!["one", "two"]);
+        assert_eq!(wrap("", 10), vec![""]);
+        assert_eq!(wrap("tab\tseparated", 9), vec!["tab", "separated"]);
+    }
+
+    #[test]
+    fn wraps_by_display_width() {
+        assert_eq!(wrap("漢字 かな", 4), vec!["漢字", "かな"]);
+        assert_eq!(wrap("漢字漢", 4), vec!["漢字", "漢"]);
+        assert_eq!(wrap("漢字", 1), vec!["漢", "字"]);
+    }
+
+    #[test]
+    fn wide_rows_never_exceed_width() {
+        let width = 6;
+        let mut string = WrappedMorphingString::new("abcdef ghijkl", width);
+        string.set_target("漢字かな 한국어");
+
+        while !string.advance().is_complete() {
+            for row in string.rows() {
+                assert!(display_width(row) <= width, "{row:?}");
+            }
+        }
+
+        assert_eq!(
+            string.rows().collect::<Vec<_>>(),
+            vec!["漢字か", "な", "한국어"]
+        );
+    }
+
+    #[test]
+    fn rows_never_exceed_width() {
+        let width = 12;
+        let mut string = WrappedMorphingString::new("If I must die, you must live", width);
+        string.set_target("to tell my story to sell my things and some strings");
+
+        while !string.advance().is_complete() {
+            for row in string.rows() {
+                assert!(row.chars().count() <= width, "{row:?}");
+            }
+        }
+
+        assert_eq!(
+            string.rows().collect::<Vec<_>>(),
+            wrap("to tell my story to sell my things and some strings", width)
+        );
+
+        string.set_target("short");
+        while !string.advance().is_complete() {}
+        assert_eq!(string.rows().collect::<Vec<_>>(), vec!["short"]);
+    }
+
+    fn frames(string: &mut WrappedMorphingString) -> Vec<Vec<String>> {
+        let mut frames = Vec::new();
+        while !string.advance().is_complete() {
+            frames.push(string.rows().map(str::to_string).collect());
+        }
+        frames.push(string.rows().map(str::to_string).collect());
+        frames
+    }
+
+    #[test]
+    fn scroll_shifts_appended_log_lines() {
+        let mut string = WrappedMorphingString::new("one\ntwo\nthree", 10);
+        string.set_target_with("two\nthree\nfour", Transition::Scroll);
+
+        assert_eq!(frames(&mut string), vec![vec!["two", "three", "four"]]);
+    }
+
+    #[test]
+    fn unrelated_line_scrolls_out_entirely() {
+        let mut string = WrappedMorphingString::new("If I must die,", 20);
+        string.set_target_with("you must live", Transition::Scroll);
+
+        assert_eq!(string.progress().remaining_edits, 1);
+        assert_eq!(frames(&mut string), vec![vec!["you must live"]]);
+    }
+
+    #[test]
+    fn scroll_drops_rows_beyond_target() {
+        let mut string = WrappedMorphingString::new("a\nb\nc", 10);
+        string.set_target_with("c", Transition::Scroll);
+
+        assert_eq!(frames(&mut string).last().unwrap(), &vec!["c"]);
+    }
+
+    #[test]
+    fn scroll_then_morphs_remaining_differences() {
+        let mut string = WrappedMorphingString::new("a\nb\nc", 10);
+        string.set_target_with("b\nx\nd", Transition::Scroll);
+
+        let frames = frames(&mut string);
+
+        assert_eq!(frames[0], vec!["b", "c", "d"]);
+        assert_eq!(frames.last().unwrap(), &vec!["b", "x", "d"]);
+    }
+}